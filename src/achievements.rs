@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::{act, Action, ActionState, Command},
+    constants::achievements,
+    pages::game::observer::{GameEvent, Observer},
+    utils::{civil_date, load_data, local_day, save_data},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Achievement {
+    FirstPipes,
+    Glide,
+    PerfectRun,
+    ChristmasDay,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 4] =
+    [Achievement::FirstPipes, Achievement::Glide, Achievement::PerfectRun, Achievement::ChristmasDay];
+
+impl Achievement {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Achievement::FirstPipes => "First steps",
+            Achievement::Glide => "Glider",
+            Achievement::PerfectRun => "Perfect run",
+            Achievement::ChristmasDay => "Merry Christmas",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Achievement::FirstPipes => format!("Pass {} pipes in a run", achievements::FIRST_PIPES),
+            Achievement::Glide => {
+                format!("Stay in the air for {} seconds without flapping", achievements::GLIDE_SECONDS)
+            },
+            Achievement::PerfectRun => "Fly through the last pipe without crashing".to_string(),
+            Achievement::ChristmasDay => "Play on Christmas Day".to_string(),
+        }
+    }
+}
+
+/// Unlocked achievements with the date they were unlocked on, saved to `<data dir>/achievements.json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Unlocked(pub BTreeMap<Achievement, (i64, u32, u32)>);
+
+impl Unlocked {
+    pub fn load() -> Self {
+        load_data(achievements::FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data(achievements::FILE, self)
+    }
+}
+
+/// Unlocks achievements as the runs of the player unfold
+pub struct AchievementTracker {
+    unlocked: Unlocked,
+    action_tx: Option<UnboundedSender<Action>>,
+    last_flap: f32, // Time of the last flap, or of the start of the run
+    date: fn() -> (i64, u32, u32),
+    persist: bool,  // Whether unlocks are saved
+    practice: bool, // Whether the run is assisted or a tutorial, which unlock nothing
+}
+
+impl AchievementTracker {
+    pub fn new(unlocked: Unlocked) -> Self {
+        AchievementTracker {
+            unlocked,
+            action_tx: None,
+            last_flap: 0.0,
+            date: || civil_date(local_day()),
+            persist: true,
+            practice: false,
+        }
+    }
+
+    /// A tracker dated `date` that saves nothing, for tests
+    #[cfg(test)]
+    pub fn unsaved(unlocked: Unlocked, date: fn() -> (i64, u32, u32)) -> Self {
+        AchievementTracker { date, persist: false, ..AchievementTracker::new(unlocked) }
+    }
+
+    fn unlock(&mut self, achievement: Achievement) {
+        if self.unlocked.0.contains_key(&achievement) {
+            return;
+        }
+        self.unlocked.0.insert(achievement, (self.date)());
+        if self.persist {
+            if let Err(e) = self.unlocked.save() {
+                log::error!("Failed to save achievements: {e}");
+            }
+        }
+        if let Some(action_tx) = &self.action_tx {
+            let _ = action_tx.send(act!(Command::AchievementUnlocked(achievement)));
+        }
+    }
+
+    fn check_glide(&mut self, time: f32) {
+        if time - self.last_flap >= achievements::GLIDE_SECONDS {
+            self.unlock(Achievement::Glide);
+        }
+    }
+}
+
+impl Observer for AchievementTracker {
+    fn notify(&mut self, event: &GameEvent) {
+        if let GameEvent::RunStarted { assisted, tutorial, .. } = *event {
+            self.practice = assisted || tutorial;
+        }
+        if self.practice {
+            return;
+        }
+
+        match *event {
+            GameEvent::RunStarted { time, .. } => {
+                self.last_flap = time;
+                let (_, month, day) = (self.date)();
+                if (month, day) == achievements::CHRISTMAS {
+                    self.unlock(Achievement::ChristmasDay);
+                }
+            },
+            GameEvent::Flap { time } => {
+                self.check_glide(time);
+                self.last_flap = time;
+            },
+            GameEvent::PipePassed { score, time } => {
+                self.check_glide(time);
+                if score >= achievements::FIRST_PIPES {
+                    self.unlock(Achievement::FirstPipes);
+                }
+            },
+            GameEvent::Collision { .. } => {},
+            GameEvent::RunFinished { crashed, .. } => {
+                if !crashed {
+                    self.unlock(Achievement::PerfectRun);
+                }
+            },
+        }
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) {
+        self.action_tx = Some(tx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::game::mutators::Mutators;
+
+    #[test]
+    fn test_tracker() {
+        let mut unlocked = Unlocked::default();
+        unlocked.0.insert(Achievement::PerfectRun, (2024, 1, 1));
+        let mut tracker = AchievementTracker::unsaved(unlocked, || (2025, 12, 25));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        tracker.register_action_handler(tx);
+
+        let events = [
+            GameEvent::RunStarted {
+                seed: 1,
+                mutators: Mutators::default(),
+                assisted: true,
+                tutorial: false,
+                time: 0.0,
+            },
+            GameEvent::PipePassed { score: 5, time: 9.0 },
+            GameEvent::RunFinished { score: 5, crashed: false, time: 10.0 },
+            GameEvent::RunStarted {
+                seed: 1,
+                mutators: Mutators::default(),
+                assisted: false,
+                tutorial: true,
+                time: 0.0,
+            },
+            GameEvent::PipePassed { score: 5, time: 9.0 },
+            GameEvent::RunStarted {
+                seed: 1,
+                mutators: Mutators::default(),
+                assisted: false,
+                tutorial: false,
+                time: 0.0,
+            },
+            GameEvent::Flap { time: 1.0 },
+            GameEvent::PipePassed { score: 1, time: 3.5 },
+            GameEvent::Flap { time: 4.5 },
+            GameEvent::PipePassed { score: 5, time: 5.0 },
+            GameEvent::RunFinished { score: 5, crashed: false, time: 6.0 },
+        ];
+        for event in events {
+            tracker.notify(&event);
+        }
+
+        // Assisted and tutorial runs unlock nothing, only the glide from 1.0 to 4.5 counts, and the perfect run was
+        // unlocked before
+        let commands = std::iter::from_fn(|| rx.try_recv().ok()).map(|action| action.command).collect::<Vec<_>>();
+        let expected = [Achievement::ChristmasDay, Achievement::Glide, Achievement::FirstPipes];
+        assert_eq!(commands, expected.map(Command::AchievementUnlocked));
+    }
+}
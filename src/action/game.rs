@@ -10,5 +10,11 @@ use crate::action::ActionState;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Display, Deserialize)]
 pub enum GameAction {
-    Up
+    Up,
+    PlayerTwoUp,
+    Pause,
+    MenuUp,
+    MenuDown,
+    Select,
+    ToggleHeatmap,
 }
@@ -10,5 +10,7 @@ use strum::Display;
 pub enum HomeAction {
     Up,
     Down,
+    Left,
+    Right,
     Select,
 }
@@ -1,6 +1,12 @@
+mod achievements;
 mod card;
 mod game;
 mod home;
+mod mutators;
+mod scores;
+mod settings;
+mod stats;
+mod tournament;
 
 use std::{fmt, string::ToString};
 
@@ -11,7 +17,11 @@ use serde::{
 };
 use strum::Display;
 
-pub use crate::action::{card::CardAction, game::GameAction, home::HomeAction};
+pub use crate::action::{
+    achievements::AchievementsAction, card::CardAction, game::GameAction, home::HomeAction, mutators::MutatorsAction,
+    scores::ScoresAction, settings::SettingsAction, stats::StatsAction, tournament::TournamentAction,
+};
+use crate::{achievements::Achievement, config::Settings, leaderboard::Entry};
 
 #[macro_export]
 macro_rules! act {
@@ -39,12 +49,14 @@ pub enum ActionState {
     End,
 }
 
-//// ANCHOR: action_enum
+// ANCHOR: action_enum
 #[derive(Debug, Clone, PartialEq, Eq, Display, Deserialize)]
 pub enum Command {
     Tick,
     Render,
     Resize(u16, u16),
+    FocusLost,
+    FocusGained,
     Suspend,
     Resume,
     Quit,
@@ -53,11 +65,38 @@ pub enum Command {
     Help,
     ToggleShowHelp,
     StartGame,
+    StartTutorial,
+    StartRace,
+    StartVersus,
+    StartDemo,
+    StartSpeedrun,
+    ContinueRun, // The single player run saved
+    StopDemo,
+    OpenTournament,
+    StartTurn(String, u64), // Name of the player and seed of the course
+    TurnOver(u16),          // Pipes passed in the turn
+    StartDaily(i64),        // Day of the challenge, since 1970-01-01
+    OpenScores,
+    ScoresLoaded(Result<Vec<Entry>, String>), // The top runs of the leaderboard, or why they could not be fetched
+    OpenAchievements,
+    AchievementUnlocked(Achievement),
+    OpenStats,
+    OpenMutators,
     ShowCard,
+    ShowHome,
+    OpenSettings,
+    CloseSettings,
+    UpdateSettings(Settings),
     // Page actions
     Home(HomeAction),
     Game(GameAction),
     Card(CardAction),
+    Settings(SettingsAction),
+    Tournament(TournamentAction),
+    Scores(ScoresAction),
+    Achievements(AchievementsAction),
+    Stats(StatsAction),
+    Mutators(MutatorsAction),
 }
 
 impl Command {
@@ -66,6 +105,12 @@ impl Command {
             Self::Home(command) => command.to_string(),
             Self::Game(command) => command.to_string(),
             Self::Card(command) => command.to_string(),
+            Self::Settings(command) => command.to_string(),
+            Self::Tournament(command) => command.to_string(),
+            Self::Scores(command) => command.to_string(),
+            Self::Achievements(command) => command.to_string(),
+            Self::Stats(command) => command.to_string(),
+            Self::Mutators(command) => command.to_string(),
             _ => self.to_string(),
         }
     }
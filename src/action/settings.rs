@@ -0,0 +1,17 @@
+use std::{fmt, string::ToString};
+
+use serde::{
+    de::{self, Deserializer, Visitor},
+    Deserialize, Serialize,
+};
+use strum::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Display, Deserialize)]
+pub enum SettingsAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
+}
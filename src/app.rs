@@ -1,5 +1,5 @@
 use color_eyre::eyre::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, KeyModifiers};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Flex, Layout, Margin},
     prelude::Rect,
@@ -12,14 +12,22 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc::{self, UnboundedSender};
 
 use crate::{
+    achievements::{AchievementTracker, Unlocked},
     action::{self, act, Action, ActionState, Command},
+    assets,
     components::{
         background::{Background, BackgroundState},
         help::Help,
+        toast::Toasts,
     },
     config::Config,
     constants::{home, HEIGHT, WIDTH},
-    pages::{card::CardPage, game::GamePage, home::HomePage, Page, PageId},
+    net::Session,
+    pages::{
+        achievements::AchievementsPage, card::CardPage, game::GamePage, home::HomePage, mutators::MutatorsPage,
+        scores::ScoresPage, settings::SettingsPage, stats::StatsPage, tournament::TournamentPage, Page, PageId,
+    },
+    stats::{History, StatsRecorder},
     tui,
 };
 
@@ -38,7 +46,11 @@ pub struct App {
     show_help: bool,
     pages: Vec<Box<dyn Page>>,
     active_page_index: usize,
+    settings_return_index: usize, // Page to return to when the settings are closed
     background_state: BackgroundState,
+    session: Option<Session>, // Versus match to start with
+    demo: bool,               // Whether the bot plays behind the home page
+    toasts: Toasts,
 }
 
 impl App {
@@ -47,8 +59,16 @@ impl App {
 
         let page_keybindings = &config.keybindings.pages;
         let home_page = HomePage::new();
-        let game_page = GamePage::new();
+        let game_page = GamePage::new()
+            .observer(Box::new(AchievementTracker::new(Unlocked::load())))
+            .observer(Box::new(StatsRecorder::new(History::load())));
         let card_page = CardPage::new();
+        let settings_page = SettingsPage::new();
+        let tournament_page = TournamentPage::new();
+        let scores_page = ScoresPage::new();
+        let achievements_page = AchievementsPage::new();
+        let stats_page = StatsPage::new();
+        let mutators_page = MutatorsPage::new();
 
         Ok(Self {
             tick_rate,
@@ -57,12 +77,30 @@ impl App {
             should_suspend: false,
             show_help: false,
             config,
-            pages: vec![Box::new(home_page), Box::new(game_page), Box::new(card_page)],
+            pages: vec![
+                Box::new(home_page),
+                Box::new(game_page),
+                Box::new(card_page),
+                Box::new(settings_page),
+                Box::new(tournament_page),
+                Box::new(scores_page),
+                Box::new(achievements_page),
+                Box::new(stats_page),
+                Box::new(mutators_page),
+            ],
             active_page_index: 0,
+            settings_return_index: 0,
             background_state: BackgroundState::new(home::SNOWFLAKE_SPEED, home::SNOWFLAKE_DENSITY),
+            session: None,
+            demo: false,
+            toasts: Toasts::default(),
         })
     }
 
+    pub fn session(self, session: Option<Session>) -> Self {
+        Self { session, ..self }
+    }
+
     fn get_active_page(&mut self) -> &mut Box<dyn Page> {
         self.pages.get_mut(self.active_page_index).unwrap()
     }
@@ -93,6 +131,13 @@ impl App {
             page.register_config_handler(self.config.clone())?;
         }
 
+        if let Some(session) = &self.session {
+            for page in self.pages.iter_mut() {
+                page.register_session_handler(session.clone())?;
+            }
+            action_tx.send(act!(Command::StartVersus))?;
+        }
+
         for page in self.pages.iter_mut() {
             page.init()?;
         }
@@ -104,19 +149,25 @@ impl App {
                     tui::Event::Tick => action_tx.send(act!(Command::Tick))?,
                     tui::Event::Render => action_tx.send(act!(Command::Render))?,
                     tui::Event::Resize(x, y) => action_tx.send(act!(Command::Resize(x, y)))?,
+                    tui::Event::FocusLost => action_tx.send(act!(Command::FocusLost))?,
+                    tui::Event::FocusGained => action_tx.send(act!(Command::FocusGained))?,
+                    // Any key stops the demo, and does nothing else
+                    tui::Event::Key(_) if self.demo => action_tx.send(act!(Command::StopDemo))?,
                     tui::Event::Key(key) => {
                         let mut action = None;
 
+                        // Only shortcuts with Ctrl get through while typing
+                        let typing = self.get_active_page().typing() && !key.modifiers.contains(KeyModifiers::CONTROL);
                         let activa_page_id = self.get_active_page().id();
                         let page_keymap = self.config.keybindings.pages.get(&activa_page_id);
                         if let Some(keymap) = self.config.keybindings.pages.get(&activa_page_id) {
-                            action = keymap.0.get(&key.into());
+                            action = keymap.0.get(&key);
                         };
-                        if let Some(act) = self.config.keybindings.global.0.get(&key.into()) {
+                        if let Some(act) = self.config.keybindings.global.0.get(&key) {
                             action = Some(act)
                         }
 
-                        if let Some(action) = action {
+                        if let Some(action) = action.filter(|_| !typing) {
                             log::info!("Got action: {action:?}");
                             action_tx.send(action.clone())?;
                         }
@@ -134,7 +185,13 @@ impl App {
                     log::debug!("{command:?}");
                 }
                 match command {
-                    Command::Tick => {},
+                    // Pages read most assets when drawing, but the skins are built from them up front
+                    Command::Tick if assets::poll() => {
+                        for page in self.pages.iter_mut() {
+                            page.register_config_handler(self.config.clone())?;
+                        }
+                    },
+                    Command::Tick => self.toasts.update(),
                     Command::Quit => self.should_quit = true,
                     Command::Suspend => self.should_suspend = true,
                     Command::Resume => self.should_suspend = false,
@@ -153,19 +210,87 @@ impl App {
                     Command::Render => {
                         self.render(&mut tui, &action_tx)?;
                     },
-                    Command::StartGame => {
+                    Command::StartGame
+                    | Command::StartTutorial
+                    | Command::StartRace
+                    | Command::StartVersus
+                    | Command::StartSpeedrun
+                    | Command::ContinueRun
+                    | Command::StartTurn(..)
+                    | Command::StartDaily(_) => {
                         self.background_state.show_snowman = false;
                         self.background_state.show_tree = false;
+                        self.background_state.show_parallax = true;
                         self.set_active_page(1);
                     },
+                    Command::UpdateSettings(settings) => {
+                        self.config.settings = settings.clone();
+                        if let Err(e) = settings.save() {
+                            log::error!("Failed to save settings: {e}");
+                        }
+                        for page in self.pages.iter_mut() {
+                            page.register_config_handler(self.config.clone())?;
+                        }
+                    },
                     Command::ShowCard => {
                         self.background_state.show_snowman = true;
                         self.background_state.show_tree = true;
+                        self.background_state.show_parallax = false;
+                        self.background_state.set_wind((0.0, 0.0));
                         self.set_active_page(2);
                     },
+                    Command::ShowHome => {
+                        self.background_state.show_snowman = true;
+                        self.background_state.show_tree = true;
+                        self.background_state.show_parallax = false;
+                        self.background_state.set_wind((0.0, 0.0));
+                        self.set_active_page(0);
+                    },
+                    Command::StartDemo => {
+                        self.demo = true;
+                        self.background_state.show_snowman = false;
+                        self.background_state.show_tree = false;
+                        self.background_state.show_parallax = true;
+                    },
+                    Command::StopDemo => {
+                        self.demo = false;
+                        self.background_state.show_snowman = true;
+                        self.background_state.show_tree = true;
+                        self.background_state.show_parallax = false;
+                        self.background_state.set_wind((0.0, 0.0));
+                    },
+                    Command::OpenTournament => self.set_active_page(4),
+                    Command::OpenScores => self.set_active_page(5),
+                    Command::OpenAchievements => self.set_active_page(6),
+                    Command::OpenStats => self.set_active_page(7),
+                    Command::OpenMutators => self.set_active_page(8),
+                    Command::AchievementUnlocked(achievement) => {
+                        self.toasts.push("Achievement unlocked", achievement.title());
+                    },
+                    Command::TurnOver(_) => {
+                        self.background_state.show_snowman = true;
+                        self.background_state.show_tree = true;
+                        self.background_state.show_parallax = false;
+                        self.background_state.set_wind((0.0, 0.0));
+                        self.set_active_page(4);
+                    },
+                    Command::OpenSettings => {
+                        self.settings_return_index = self.active_page_index;
+                        self.set_active_page(3);
+                    },
+                    Command::CloseSettings => self.set_active_page(self.settings_return_index),
                     _ => {},
                 }
-                if !self.show_help {
+                // Behind the help, pages that cannot be paused keep running without the input meant for the help
+                let ticking = matches!(command, Command::Tick | Command::Render);
+                let running = |page: &dyn Page| !self.show_help || (ticking && page.runs_behind_help());
+
+                // The demo game runs behind the home page
+                let behind = (self.demo || *command == Command::StopDemo) && self.active_page_index == 0;
+                if behind && running(self.pages[1].as_ref()) {
+                    self.pages[1].update(action.clone())?;
+                }
+                if running(self.pages[self.active_page_index].as_ref()) {
                     if let Some(action) = self.get_active_page().update(action)? {
                         action_tx.send(action)?
                     }
@@ -188,6 +313,12 @@ impl App {
     }
 
     fn render(&mut self, tui: &mut tui::Tui, action_tx: &UnboundedSender<Action>) -> Result<()> {
+        let demo = self.demo && self.active_page_index == 0;
+        let background_page_index = if demo { 1 } else { self.active_page_index };
+        if let Some(page) = self.pages.get_mut(background_page_index) {
+            page.update_background(&mut self.background_state);
+        }
+
         tui.draw(|f| {
             let area = f.area();
 
@@ -204,11 +335,16 @@ impl App {
 
             let area = area.inner(Margin { horizontal: 1, vertical: 1 });
 
-            let background = Background::default();
+            let background = Background;
             f.render_stateful_widget(background, area, &mut self.background_state);
 
             let area = self.background_state.get_empty_area(area);
 
+            if demo {
+                if let Err(e) = self.pages[1].draw(f, area) {
+                    action_tx.send(act!(Command::Error(format!("Failed to draw: {:?}", e)))).unwrap();
+                }
+            }
             if let Some(page) = self.pages.get_mut(self.active_page_index) {
                 let r = page.draw(f, area);
                 if let Err(e) = r {
@@ -216,6 +352,8 @@ impl App {
                 }
             }
 
+            f.render_widget(&self.toasts, area);
+
             if self.show_help {
                 let r = self.draw_help(f, area);
                 if let Err(e) = r {
@@ -0,0 +1,242 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use lazy_static::lazy_static;
+use ratatui::{layout::Offset, style::Color};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{background, card, game, title},
+    utils::get_config_dir,
+};
+
+pub const TITLE: &str = "title";
+pub const CONGRAT: &str = "congrat";
+pub const SNOWMAN: &str = "snowman";
+pub const TREE: &str = "tree";
+pub const BIRD: &str = "bird";
+
+const NAMES: [&str; 5] = [TITLE, CONGRAT, SNOWMAN, TREE, BIRD];
+const RELOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// ASCII art loaded from `<config dir>/assets/<name>.yaml`, falling back to the compiled-in constants:
+///
+/// ```yaml
+/// layers:
+///   - |2
+///      .-.
+///     (   )
+/// colors: [White]
+/// anchor: [0, 1]
+/// ```
+///
+/// Layers are drawn from first to last, and the anchor shifts the art from its position in the layout.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Asset {
+    pub layers: Vec<String>,
+    #[serde(default)]
+    pub colors: Vec<Option<Color>>,
+    #[serde(default)]
+    pub anchor: (i32, i32),
+}
+
+impl Asset {
+    pub fn new(layers: &[&str], colors: &[Option<Color>], anchor: (i32, i32)) -> Self {
+        Asset { layers: layers.iter().map(|layer| layer.to_string()).collect(), colors: colors.to_vec(), anchor }
+    }
+
+    pub fn builtin(name: &str) -> Option<Asset> {
+        let asset = match name {
+            TITLE => {
+                Asset::new(&[title::TITLE_SHADOW, title::TITLE_TEXT], &[Some(Color::Green), Some(Color::Red)], (0, 0))
+            },
+            CONGRAT => {
+                Asset::new(&[card::CONGRAT_SHADOW, card::CONGRAT_TEXT], &[Some(Color::Green), Some(Color::Red)], (0, 0))
+            },
+            SNOWMAN => Asset::new(&[background::SNOWMAN], &[Some(Color::White)], (0, 1)),
+            TREE => {
+                Asset::new(
+                    &[background::TREETOP_TEXT, background::TREEBODY_TEXT, background::TREEBOTTOM_TEXT],
+                    &[Some(Color::Yellow), Some(Color::Green), Some(Color::Rgb(102, 56, 21))],
+                    (0, 1),
+                )
+            },
+            BIRD => Asset::new(&game::BIRD_TEXTS, &game::BIRD_COLORS, (0, 0)),
+            _ => return None,
+        };
+        Some(asset)
+    }
+
+    pub fn load(path: &Path) -> Result<Asset> {
+        let content = std::fs::read_to_string(path)?;
+        let mut asset: Asset = serde_yaml::from_str(&content)?;
+        pad_layers(asset.layers.iter_mut().collect())?;
+        // Layers without a color are drawn in the default one
+        asset.colors.resize(asset.layers.len(), None);
+        Ok(asset)
+    }
+
+    /// Non-empty lines of each layer
+    pub fn lines(&self) -> Vec<Vec<&str>> {
+        self.layers.iter().map(|layer| layer.lines().filter(|line| !line.is_empty()).collect()).collect()
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        let lines = self.lines();
+        let height = lines.iter().map(|lines| lines.len()).max().unwrap_or(0) as u16;
+        let width = lines.iter().flatten().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
+        (width, height)
+    }
+
+    pub fn color(&self, index: usize) -> Color {
+        self.colors.get(index).copied().flatten().unwrap_or(Color::White)
+    }
+
+    pub fn offset(&self) -> Offset {
+        Offset { x: self.anchor.0, y: self.anchor.1 }
+    }
+}
+
+/// Pad the non-empty lines of `layers` with spaces to the size of the largest layer, so that the layers line up and are
+/// clipped alike. Fails if there are no layers or one of them is empty.
+pub fn pad_layers(layers: Vec<&mut String>) -> Result<()> {
+    if layers.is_empty() || layers.iter().any(|layer| layer.trim().is_empty()) {
+        return Err(eyre!("A layer is empty"));
+    }
+    let lines = |layer: &String| layer.lines().filter(|line| !line.is_empty()).map(str::to_string).collect::<Vec<_>>();
+    let height = layers.iter().map(|layer| lines(layer).len()).max().unwrap_or(0);
+    let width = layers.iter().flat_map(|layer| lines(layer)).map(|line| line.chars().count()).max().unwrap_or(0);
+    for layer in layers {
+        let padded = lines(layer).into_iter().chain(std::iter::repeat(String::new())).take(height);
+        *layer = padded.map(|line| format!("{line:width$}\n")).collect();
+    }
+    Ok(())
+}
+
+struct Entry {
+    asset: Asset,
+    modified: Option<SystemTime>,
+}
+
+struct Registry {
+    directory: PathBuf,
+    entries: HashMap<&'static str, Entry>,
+    last_check: Option<Instant>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        let mut registry =
+            Registry { directory: get_config_dir().join("assets"), entries: HashMap::new(), last_check: None };
+        registry.reload();
+        registry
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.directory.join(format!("{name}.yaml"))
+    }
+
+    /// Reload the assets whose files were added, changed or removed, and return whether any of them did
+    fn reload(&mut self) -> bool {
+        self.last_check = Some(Instant::now());
+
+        let mut changed = false;
+        for name in NAMES {
+            let path = self.path(name);
+            let modified = std::fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+            if let Some(entry) = self.entries.get(name) {
+                if entry.modified == modified {
+                    continue;
+                }
+            }
+
+            let asset = match modified.map(|_| Asset::load(&path)) {
+                Some(Ok(asset)) => asset,
+                Some(Err(e)) => {
+                    log::warn!("Failed to load asset {}, using the built-in one: {e}", path.display());
+                    Asset::builtin(name).unwrap()
+                },
+                None => Asset::builtin(name).unwrap(),
+            };
+            changed |= self.entries.get(name).is_some_and(|entry| entry.asset != asset);
+            self.entries.insert(name, Entry { asset, modified });
+        }
+        changed
+    }
+}
+
+lazy_static! {
+    static ref REGISTRY: Mutex<Registry> = Mutex::new(Registry::new());
+}
+
+/// The asset called `name`, from its file if there is a valid one
+pub fn get(name: &str) -> Asset {
+    let registry = REGISTRY.lock().unwrap();
+    match registry.entries.get(name) {
+        Some(entry) => entry.asset.clone(),
+        None => Asset::builtin(name).expect("unknown asset"),
+    }
+}
+
+/// Check the asset files for changes, at most once per `RELOAD_INTERVAL`. Returns whether an asset changed.
+pub fn poll() -> bool {
+    let mut registry = REGISTRY.lock().unwrap();
+    if registry.last_check.is_some_and(|last_check| last_check.elapsed() < RELOAD_INTERVAL) {
+        return false;
+    }
+    registry.reload()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_asset() {
+        let asset: Asset = serde_yaml::from_str(
+            r#"
+layers:
+  - |2
+     .-.
+    (   )
+colors: [White]
+anchor: [2, -1]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(asset.size(), (5, 2));
+        assert_eq!(asset.color(0), Color::White);
+        assert_eq!(asset.color(1), Color::White);
+        assert_eq!(asset.offset(), Offset { x: 2, y: -1 });
+    }
+
+    #[test]
+    fn test_builtin_assets() {
+        for name in NAMES {
+            let asset = Asset::builtin(name).unwrap();
+            assert_eq!(asset.layers.len(), asset.colors.len());
+        }
+    }
+
+    #[test]
+    fn test_load_asset() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("asset-{}.yaml", std::process::id()));
+        std::fs::write(&path, "layers: [\" .-.\\n(   )\", \"\\n o o \\n\\n ^\"]\ncolors: []\n")?;
+        let asset = Asset::load(&path);
+        std::fs::write(&path, "layers: [\" .-.\", \" \\n\"]\n")?;
+        let empty = Asset::load(&path);
+        std::fs::remove_file(&path)?;
+
+        let asset = asset?;
+        assert_eq!(asset.layers, vec![" .-. \n(   )\n", " o o \n ^   \n"]);
+        assert_eq!(asset.colors, vec![None, None]);
+        assert!(empty.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::path::PathBuf;
+
+use clap::{value_parser, Arg, ArgMatches, Command};
+use color_eyre::eyre::Result;
+
+use crate::{
+    config::Config,
+    constants::{leaderboard, HEIGHT, WIDTH},
+    convert::convert_image,
+    net::{Session, DEFAULT_PORT},
+    pages::game::simulation,
+    utils::get_data_dir,
+};
+
+pub fn cli() -> Command {
+    Command::new(env!("CARGO_PKG_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand(
+            Command::new("host").about("Host a versus match on the local network").arg(
+                Arg::new("port")
+                    .long("port")
+                    .short('p')
+                    .help(format!("Port to listen on, {DEFAULT_PORT} by default"))
+                    .value_parser(value_parser!(u16)),
+            ),
+        )
+        .subcommand(
+            Command::new("join")
+                .about("Join a versus match")
+                .arg(Arg::new("address").required(true).help("Address of the host, with an optional port")),
+        )
+        .subcommand(
+            Command::new("leaderboard")
+                .about("Run a leaderboard server that checks the replay of every run sent to it")
+                .arg(
+                    Arg::new("port")
+                        .long("port")
+                        .short('p')
+                        .help(format!("Port to listen on, {} by default", leaderboard::DEFAULT_PORT))
+                        .value_parser(value_parser!(u16)),
+                )
+                .arg(
+                    Arg::new("file")
+                        .long("file")
+                        .short('f')
+                        .help(format!("File to keep the scores in, {} in the data dir by default", leaderboard::FILE))
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("sim")
+                .about("Run the game without a terminal, speaking line-delimited JSON over stdin and stdout")
+                .arg(
+                    Arg::new("width")
+                        .long("width")
+                        .help(format!("Width in cells, {WIDTH} by default"))
+                        .value_parser(value_parser!(u16)),
+                )
+                .arg(
+                    Arg::new("height")
+                        .long("height")
+                        .help(format!("Height in cells, {HEIGHT} by default"))
+                        .value_parser(value_parser!(u16)),
+                ),
+        )
+        .subcommand(
+            Command::new("convert")
+                .about("Convert a PNG image into a skin file with one layer per terminal color")
+                .arg(Arg::new("input").required(true).value_parser(value_parser!(PathBuf)))
+                .arg(Arg::new("width").long("width").help("Width in cells").value_parser(value_parser!(u32)))
+                .arg(Arg::new("height").long("height").help("Height in cells").value_parser(value_parser!(u32)))
+                .arg(Arg::new("name").long("name").help("Skin name, the file name by default"))
+                .arg(
+                    Arg::new("output")
+                        .long("output")
+                        .short('o')
+                        .help("Output file, stdout by default")
+                        .value_parser(value_parser!(PathBuf)),
+                ),
+        )
+}
+
+/// Host with the wind of the difficulty picked in the settings, which the joining side flies too
+pub fn host(args: &ArgMatches) -> Result<Session> {
+    let wind = Config::new()?.wind();
+    Ok(Session::host(args.get_one::<u16>("port").copied().unwrap_or(DEFAULT_PORT), wind))
+}
+
+pub fn join(args: &ArgMatches) -> Session {
+    Session::join(args.get_one::<String>("address").unwrap())
+}
+
+pub async fn leaderboard(args: &ArgMatches) -> Result<()> {
+    let port = args.get_one::<u16>("port").copied().unwrap_or(leaderboard::DEFAULT_PORT);
+    let path = args.get_one::<PathBuf>("file").cloned().unwrap_or(get_data_dir().join(leaderboard::FILE));
+    crate::leaderboard::serve(port, &path).await
+}
+
+pub fn sim(args: &ArgMatches) -> Result<()> {
+    let width = args.get_one::<u16>("width").copied().unwrap_or(WIDTH);
+    let height = args.get_one::<u16>("height").copied().unwrap_or(HEIGHT);
+    simulation::serve(width, height)
+}
+
+pub fn convert(args: &ArgMatches) -> Result<()> {
+    let input = args.get_one::<PathBuf>("input").unwrap();
+    let width = args.get_one::<u32>("width").copied();
+    let height = args.get_one::<u32>("height").copied();
+    let name = args.get_one::<String>("name").map(|name| name.as_str());
+
+    let skin = convert_image(input, width, height, name)?;
+    let content = serde_yaml::to_string(&skin)?;
+    match args.get_one::<PathBuf>("output") {
+        Some(output) => std::fs::write(output, content)?,
+        None => print!("{content}"),
+    }
+    Ok(())
+}
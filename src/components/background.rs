@@ -12,9 +12,10 @@ use ratatui::{
 
 use crate::{
     action::{ActionState, Command},
+    assets::{self, Asset},
     components::multiline::MultiLine,
     config::{key_event_to_string, Config, PageKeyBindings},
-    constants::background,
+    constants::background::{self, ParallaxLayer},
     pages::PageId,
 };
 
@@ -27,8 +28,12 @@ pub struct BackgroundState {
     width: usize,
     height: usize,
     current: usize,
+    scroll: f32,      // Horizontal scroll position of the scenery: columns
+    wind: (f32, f32), // Direction and intensity of the wind in world coordinates
+    drift: f32,       // Horizontal drift of the snowflakes not applied yet: columns
     pub show_snowman: bool,
     pub show_tree: bool,
+    pub show_parallax: bool,
 }
 
 impl BackgroundState {
@@ -41,14 +46,17 @@ impl BackgroundState {
             width: 0,
             height: 0,
             current: 0,
+            scroll: 0.0,
+            wind: (0.0, 0.0),
+            drift: 0.0,
             show_snowman: true,
             show_tree: true,
+            show_parallax: false,
         }
     }
 
     fn get_delta_time(&self, now: SystemTime) -> f32 {
-        let dt = now.duration_since(self.last_time).unwrap().as_secs_f32();
-        dt
+        now.duration_since(self.last_time).unwrap().as_secs_f32()
     }
 
     fn sample(&self, rng: &mut ThreadRng) -> usize {
@@ -105,32 +113,76 @@ impl BackgroundState {
         let now = SystemTime::now();
         let dt = self.get_delta_time(now);
 
-        if dt >= 1.0 / self.speed {
+        // An updraft slows the snowfall down or even lifts the snow up
+        let speed = self.speed * (1.0 - self.wind.1 * background::WIND_LIFT);
+        if speed.abs() < f32::EPSILON {
+            self.last_time = now;
+        } else if dt >= 1.0 / speed.abs() {
             self.last_time = now;
 
             let new_row = std::iter::repeat_with(|| self.sample(&mut rng)).take(width).collect();
-            self.snowflakes = {
+            self.snowflakes = if speed > 0.0 {
                 let mut snowflakes = vec![new_row];
-                snowflakes.extend(self.snowflakes.iter().map(|row| row.clone()).take(height - 1));
+                snowflakes.extend(self.snowflakes.iter().take(height - 1).cloned());
+                snowflakes
+            } else {
+                let mut snowflakes = self.snowflakes.iter().skip(1).cloned().collect::<Vec<_>>();
+                snowflakes.push(new_row);
                 snowflakes
             };
+
+            // A headwind blows the snow sideways
+            self.drift += self.wind.0 * background::WIND_DRIFT;
+            let shift = self.drift.trunc();
+            self.drift -= shift;
+            if shift != 0.0 && width > 0 {
+                let shift = shift as isize;
+                for row in self.snowflakes.iter_mut() {
+                    if shift > 0 {
+                        row.rotate_right(shift as usize % width);
+                    } else {
+                        row.rotate_left(-shift as usize % width);
+                    }
+                }
+            }
         }
 
+        let intensity = self.wind.0.abs().max(self.wind.1.abs());
+        let streak = if intensity < background::WIND_STREAK_THRESHOLD {
+            None
+        } else if self.wind.0.abs() >= self.wind.1.abs() {
+            Some(background::HORIZONTAL_STREAK)
+        } else {
+            Some(background::VERTICAL_STREAK)
+        };
+
         self.snowflakes
             .iter()
             .map(|row| {
                 row.clone()
                     .into_iter()
-                    .map(
-                        |index| if index == background::SNOWFLAKES.len() { ' ' } else { background::SNOWFLAKES[index] },
-                    )
+                    .map(|index| {
+                        if index == background::SNOWFLAKES.len() {
+                            ' '
+                        } else {
+                            streak.unwrap_or(background::SNOWFLAKES[index])
+                        }
+                    })
                     .collect::<String>()
             })
             .collect::<Vec<_>>()
     }
 
+    pub fn set_scroll(&mut self, scroll: f32) {
+        self.scroll = scroll;
+    }
+
+    pub fn set_wind(&mut self, wind: (f32, f32)) {
+        self.wind = wind;
+    }
+
     pub fn get_empty_area(&self, area: Rect) -> Rect {
-        let height = area.height as u16;
+        let height = area.height;
         let sky_height = height - background::GROUND_HEIGHT;
         Rect { height: sky_height, ..area }
     }
@@ -141,13 +193,13 @@ pub struct Background;
 
 impl Background {
     pub fn new() -> Self {
-        Self::default()
+        Self
     }
 
     // fn render_snowman(&self, area: Rect, buf: &mut Buffer, snowman_lines: Vec<&str>) {
     fn render_snowman(&self, area: Rect, buf: &mut Buffer) {
-        let (snowman_lines, num_snowman_lines) = filter_text(background::SNOWMAN);
-        let max_width = snowman_lines.iter().map(|line| line.len()).max().unwrap_or(0) as u16;
+        let snowman = assets::get(assets::SNOWMAN);
+        let (max_width, num_snowman_lines) = snowman.size();
 
         let [_, area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(num_snowman_lines)]).areas(area);
         let [_, area, _] = Layout::horizontal([
@@ -156,12 +208,9 @@ impl Background {
             Constraint::Fill(1),
         ])
         .areas(area);
-        let area = area.offset(Offset { x: 0, y: 1 });
+        let area = area.offset(snowman.offset());
 
-        MultiLine::new(snowman_lines)
-            .style(Style::default().fg(Color::White))
-            .ignore_whitespace(true)
-            .render(area, buf);
+        render_layers(area, buf, &snowman);
     }
 
     fn render_ground(&self, area: Rect, buf: &mut Buffer) {
@@ -185,25 +234,53 @@ impl Background {
         paragraph.render(area, buf);
     }
 
+    fn render_parallax_layer(&self, area: Rect, buf: &mut Buffer, layer: &ParallaxLayer, scroll: f32) {
+        let texts = layer.texts.iter().map(|(text, color)| (filter_text(text).0, *color)).collect::<Vec<_>>();
+        let num_lines = texts.iter().map(|(lines, _)| lines.len()).max().unwrap_or(0) as u16;
+        let tile_width =
+            texts.iter().flat_map(|(lines, _)| lines.iter().map(|line| line.chars().count())).max().unwrap_or(0);
+        if tile_width == 0 {
+            return;
+        }
+        let offset = (scroll * layer.speed) as usize % tile_width;
+
+        let [_, area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(num_lines)]).areas(area);
+        for (lines, color) in texts {
+            for (row, line) in lines.iter().enumerate().take(area.height as usize) {
+                // Repeat the tile horizontally, starting from the scrolled offset
+                let tile = line.chars().chain(std::iter::repeat(' ')).take(tile_width).collect::<Vec<_>>();
+                for col in 0..area.width {
+                    let c = tile[(col as usize + offset) % tile_width];
+                    if c.is_whitespace() {
+                        continue;
+                    }
+                    if let Some(cell) = buf.cell_mut((area.x + col, area.y + row as u16)) {
+                        if c == background::PARALLAX_FILL {
+                            cell.set_char(' ');
+                        } else {
+                            cell.set_char(c).set_fg(color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_parallax(&self, area: Rect, buf: &mut Buffer, state: &BackgroundState) {
+        for layer in background::PARALLAX_LAYERS.iter() {
+            self.render_parallax_layer(area, buf, layer, state.scroll);
+        }
+    }
+
     fn render_snowflakes(&self, area: Rect, buf: &mut Buffer, state: &mut BackgroundState) {
-        let lines = state.update(area).into_iter().map(|s| Line::from(s)).collect::<Vec<_>>();
+        let lines = state.update(area).into_iter().map(Line::from).collect::<Vec<_>>();
         let paragraph = Paragraph::new(lines);
         paragraph.render(area, buf);
     }
 
     fn render_tree(&self, area: Rect, buf: &mut Buffer) {
-        let (treetop_lines, num_treetop_lines) = filter_text(background::TREETOP_TEXT);
-        let (treebody_lines, num_treebody_lines) = filter_text(background::TREEBODY_TEXT);
-        let (treebottom_lines, num_treebottom_lines) = filter_text(background::TREEBOTTOM_TEXT);
-        let num_tree_lines = num_treetop_lines.max(num_treebody_lines).max(num_treebottom_lines);
-
-        let max_width = treetop_lines
-            .iter()
-            .chain(treebody_lines.iter())
-            .chain(treebottom_lines.iter())
-            .map(|line| line.len())
-            .max()
-            .unwrap_or(0) as u16;
+        let tree = assets::get(assets::TREE);
+        let (max_width, num_tree_lines) = tree.size();
 
         let [_, area] = Layout::vertical([Constraint::Fill(1), Constraint::Length(num_tree_lines)]).areas(area);
         let [_, area, _] = Layout::horizontal([
@@ -212,25 +289,20 @@ impl Background {
             Constraint::Length(background::TREE_MARGIN),
         ])
         .areas(area);
-        let area = area.offset(Offset { x: 0, y: 1 });
-
-        MultiLine::new(treetop_lines)
-            .style(Style::default().fg(Color::Yellow))
-            .ignore_whitespace(true)
-            .render(area, buf);
-        MultiLine::new(treebody_lines)
-            .style(Style::default().fg(Color::Green))
-            .ignore_whitespace(true)
-            .render(area, buf);
-        MultiLine::new(treebottom_lines)
-            .style(Style::default().fg(Color::Rgb(102, 56, 21)))
-            .ignore_whitespace(true)
-            .render(area, buf);
+        let area = area.offset(tree.offset());
+
+        render_layers(area, buf, &tree);
+    }
+}
+
+fn render_layers(area: Rect, buf: &mut Buffer, asset: &Asset) {
+    for (index, lines) in asset.lines().into_iter().enumerate() {
+        MultiLine::new(lines).style(Style::default().fg(asset.color(index))).ignore_whitespace(true).render(area, buf);
     }
 }
 
-fn filter_text(text: &'static str) -> (Vec<&str>, u16) {
-    let lines: Vec<&str> = text.lines().filter(|s| s.len() != 0).collect();
+fn filter_text(text: &'static str) -> (Vec<&'static str>, u16) {
+    let lines: Vec<&str> = text.lines().filter(|s| !s.is_empty()).collect();
     let num_lines = lines.len() as u16;
 
     (lines, num_lines)
@@ -247,7 +319,12 @@ impl StatefulWidget for Background {
             Layout::vertical([Constraint::Fill(1), Constraint::Length(background::GROUND_HEIGHT)]).areas(area);
 
         self.render_snowflakes(area, buf, state);
-        self.render_ground(ground_area, buf);
+        if state.show_parallax {
+            self.render_parallax(area, buf, state);
+            self.render_parallax_layer(ground_area, buf, &background::GROUND_LAYER, state.scroll);
+        } else {
+            self.render_ground(ground_area, buf);
+        }
 
         if state.show_snowman {
             self.render_snowman(area, buf);
@@ -0,0 +1,94 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph, Widget},
+};
+
+use crate::components::multiline::MultiLine;
+
+/// A box of options, centered in the area it is rendered to
+#[derive(Debug)]
+pub struct Menu {
+    title: String,
+    text: Vec<String>,
+    options: Vec<String>,
+    selected: usize,
+    margin_vertical: u16,
+    margin_horizontal: u16,
+}
+
+impl Menu {
+    pub fn new(title: &str, options: Vec<String>, selected: usize) -> Self {
+        Self { title: title.to_string(), text: Vec::new(), options, selected, margin_vertical: 1, margin_horizontal: 2 }
+    }
+
+    /// Lines shown above the options
+    pub fn text(self, text: Vec<String>) -> Self {
+        Self { text, ..self }
+    }
+
+    fn text_height(&self) -> u16 {
+        if self.text.is_empty() {
+            0
+        } else {
+            self.text.len() as u16 + 1
+        }
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        let option_width = self.options.iter().map(|option| option.chars().count()).max().unwrap_or(0) as u16 + 2 * 2;
+        let text_width = self.text.iter().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
+        let width =
+            option_width.max(text_width).max(self.title.chars().count() as u16) + (self.margin_horizontal + 1) * 2;
+        let height =
+            self.text_height() + (self.options.len() as u16 * 2).saturating_sub(1) + (self.margin_vertical + 1) * 2;
+        (width, height)
+    }
+}
+
+impl Widget for Menu {
+    fn render(self, area: Rect, buf: &mut Buffer)
+    where
+        Self: Sized,
+    {
+        let (width, height) = self.size();
+        let [area] = Layout::horizontal([Constraint::Length(width)]).flex(Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)]).flex(Flex::Center).areas(area);
+
+        let block = Block::new()
+            .title(self.title.clone())
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Cyan))
+            .padding(Padding::symmetric(self.margin_horizontal, self.margin_vertical))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        Clear.render(area, buf);
+        block.render(area, buf);
+
+        let [text_area, inner] =
+            Layout::vertical([Constraint::Length(self.text_height()), Constraint::Fill(1)]).areas(inner);
+        Paragraph::new(self.text.iter().map(|line| Line::from(line.as_str())).collect::<Vec<_>>())
+            .alignment(Alignment::Center)
+            .render(text_area, buf);
+
+        let option_width = inner.width as usize;
+        let options = self
+            .options
+            .iter()
+            .map(|option| format!("{:^option_width$}", option, option_width = option_width))
+            .collect::<Vec<_>>();
+        let styles = (0..options.len())
+            .map(|index| {
+                match index == self.selected {
+                    true => Style::default().fg(Color::Black).bg(Color::Blue),
+                    false => Style::default().fg(Color::White).bg(Color::DarkGray),
+                }
+            })
+            .collect();
+        MultiLine::new(options).line_padding(1).line_styles(styles).render(inner, buf);
+    }
+}
@@ -1,3 +1,5 @@
-pub mod help;
 pub mod background;
+pub mod help;
+pub mod menu;
 pub mod multiline;
+pub mod toast;
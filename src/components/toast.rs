@@ -0,0 +1,49 @@
+use std::{collections::VecDeque, time::SystemTime};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
+};
+
+use crate::constants::achievements;
+
+/// Short notifications stacked in the top right corner, each shown for a while
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<(String, String, SystemTime)>, // Title, text and when it was shown
+}
+
+impl Toasts {
+    pub fn push(&mut self, title: &str, text: &str) {
+        self.queue.push_back((title.to_string(), text.to_string(), SystemTime::now()));
+    }
+
+    /// Drop the toasts that were shown long enough
+    pub fn update(&mut self) {
+        self.queue.retain(|(_, _, shown)| shown.elapsed().unwrap_or_default() < achievements::TOAST_DURATION);
+    }
+}
+
+impl Widget for &Toasts {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut y = area.y;
+        for (title, text) in self.queue.iter().map(|(title, text, _)| (title, text)) {
+            let width = (title.chars().count().max(text.chars().count()) as u16 + 4).min(area.width);
+            let toast = Rect::new(area.right().saturating_sub(width), y, width, 3).intersection(area);
+            if toast.height < 3 {
+                break;
+            }
+            let block = Block::default()
+                .title(title.as_str())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::new().bold().fg(Color::Yellow))
+                .style(Style::default().bg(Color::Black));
+            Clear.render(toast, buf);
+            Paragraph::new(text.as_str()).alignment(Alignment::Center).block(block).render(toast, buf);
+            y += 3;
+        }
+    }
+}
@@ -12,17 +12,31 @@ use serde::{
 use serde_json::Value as JsonValue;
 
 use crate::{
-    action::{self, Action, ActionState, CardAction, Command, GameAction, HomeAction},
+    action::{
+        self, AchievementsAction, Action, ActionState, CardAction, Command, GameAction, HomeAction, MutatorsAction,
+        ScoresAction, SettingsAction, StatsAction, TournamentAction,
+    },
     app::Mode,
-    pages::{game, home, Page, PageId},
+    constants,
+    pages::{
+        game::{self, mutators::Mutators},
+        home, Page, PageId,
+    },
 };
 
 const CONFIG: &str = include_str!("../.config/config.yaml");
+const SETTINGS_FILE: &str = "settings.json";
 
 parse_and_map_actions![
     (PageId::Home, HomeAction, Command::Home),
     (PageId::Game, GameAction, Command::Game),
-    (PageId::Card, CardAction, Command::Card)
+    (PageId::Card, CardAction, Command::Card),
+    (PageId::Settings, SettingsAction, Command::Settings),
+    (PageId::Tournament, TournamentAction, Command::Tournament),
+    (PageId::Scores, ScoresAction, Command::Scores),
+    (PageId::Achievements, AchievementsAction, Command::Achievements),
+    (PageId::Stats, StatsAction, Command::Stats),
+    (PageId::Mutators, MutatorsAction, Command::Mutators)
 ];
 
 #[derive(Clone, Debug, Deserialize, Default)]
@@ -33,12 +47,170 @@ pub struct AppConfig {
     pub _config_dir: PathBuf,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RenderMode {
+    /// Draw sprites with their own characters, one row per cell
+    #[default]
+    Text,
+    /// Draw sprites with half blocks, two rows per cell
+    HalfBlock,
+    /// Draw sprites with braille dots, four rows per cell
+    Braille,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn default_wind(&self) -> WindConfig {
+        match self {
+            Difficulty::Easy => constants::game::WIND_EASY,
+            Difficulty::Normal => constants::game::WIND_NORMAL,
+            Difficulty::Hard => constants::game::WIND_HARD,
+        }
+    }
+
+    /// The difficulty whose preset is `wind`, if there is one
+    pub fn of_wind(wind: WindConfig) -> Option<Difficulty> {
+        [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard]
+            .into_iter()
+            .find(|difficulty| difficulty.default_wind() == wind)
+    }
+}
+
+/// Wind gusts of a difficulty, calm by default
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct WindConfig {
+    pub interval: f32, // Average seconds between gusts, no gusts if 0
+    pub duration: f32, // Seconds
+    pub lift: f32,     // Peak vertical acceleration: rows per second squared
+    pub drag: f32,     // Peak fraction of the pipe speed taken by a headwind
+}
+
+/// Help for single player runs, so that everyone can make it to the card
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Assist {
+    #[serde(default = "default_assist_speed")]
+    pub speed: u8, // Percent of the normal game speed
+    #[serde(default)]
+    pub wider_gaps: bool,
+    #[serde(default)]
+    pub invincible: bool,
+    #[serde(default)]
+    pub skip_after: u32, // Failed runs in a row before skipping to the card is offered, never if 0
+}
+
+fn default_assist_speed() -> u8 {
+    100
+}
+
+impl Default for Assist {
+    fn default() -> Self {
+        Assist { speed: default_assist_speed(), wider_gaps: false, invincible: false, skip_after: 0 }
+    }
+}
+
+impl Assist {
+    /// Whether runs are made easier
+    pub fn active(&self) -> bool {
+        self.speed < 100 || self.wider_gaps || self.invincible
+    }
+
+    /// Factor of every time step
+    pub fn time_scale(&self) -> f32 {
+        self.speed as f32 / 100.0
+    }
+
+    /// Rows added to every pipe gap
+    pub fn extra_gap(&self) -> u16 {
+        if self.wider_gaps {
+            constants::game::ASSIST_EXTRA_GAP
+        } else {
+            0
+        }
+    }
+}
+
+/// Where finished runs are sent, and under which name
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct LeaderboardConfig {
+    #[serde(default)]
+    pub server: Option<String>, // Address of a `leaderboard` server, runs are not sent without one
+    #[serde(default = "default_player_name")]
+    pub name: String,
+}
+
+fn default_player_name() -> String {
+    std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or("Anonymous".to_string())
+}
+
+impl Default for LeaderboardConfig {
+    fn default() -> Self {
+        LeaderboardConfig { server: None, name: default_player_name() }
+    }
+}
+
+/// Preferences that can also be changed in the app. Changes made in the app are saved to `<data dir>/settings.json`,
+/// which takes precedence over the `settings` section of the config file.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Settings {
+    #[serde(default)]
+    pub render_mode: RenderMode,
+    #[serde(default)]
+    pub skin: String,
+    #[serde(default)]
+    pub difficulty: Difficulty,
+    #[serde(default = "default_pause_on_focus_loss")]
+    pub pause_on_focus_loss: bool,
+    #[serde(default)]
+    pub minimal_hud: bool, // Only the timer in speedruns
+    #[serde(default)]
+    pub mutators: Mutators, // Picked for single player runs
+    #[serde(default)]
+    pub assist: Assist, // For single player runs
+}
+
+fn default_pause_on_focus_loss() -> bool {
+    true
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            render_mode: RenderMode::default(),
+            skin: String::new(),
+            difficulty: Difficulty::default(),
+            pause_on_focus_loss: default_pause_on_focus_loss(),
+            minimal_hud: false,
+            mutators: Mutators::default(),
+            assist: Assist::default(),
+        }
+    }
+}
+
+impl Settings {
+    pub fn save(&self) -> Result<()> {
+        crate::utils::save_data(SETTINGS_FILE, self)
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 pub struct Config {
     #[serde(default, flatten)]
     pub config: AppConfig,
     #[serde(default)]
     pub keybindings: KeyBindings,
+    #[serde(default)]
+    pub settings: Settings,
+    #[serde(default)]
+    pub weather: HashMap<Difficulty, WindConfig>,
+    #[serde(default)]
+    pub leaderboard: LeaderboardConfig,
 }
 
 impl Config {
@@ -66,16 +238,25 @@ impl Config {
         for (scope, default_bindings) in default_config.keybindings.pages.iter() {
             let user_bindings = cfg.keybindings.pages.entry(scope.clone()).or_default();
             for (key, cmd) in default_bindings.0.iter() {
-                user_bindings.0.entry(key.clone()).or_insert_with(|| cmd.clone());
+                user_bindings.0.entry(*key).or_insert_with(|| cmd.clone());
             }
         }
         let user_bindings = &mut cfg.keybindings.global;
         for (key, cmd) in default_config.keybindings.global.0.iter() {
-            user_bindings.0.entry(key.clone()).or_insert_with(|| cmd.clone());
+            user_bindings.0.entry(*key).or_insert_with(|| cmd.clone());
+        }
+        if let Some(settings) = crate::utils::load_data(SETTINGS_FILE) {
+            cfg.settings = settings;
         }
 
         Ok(cfg)
     }
+
+    /// Wind of the selected difficulty, from the `weather` section if it is there
+    pub fn wind(&self) -> WindConfig {
+        let difficulty = self.settings.difficulty;
+        self.weather.get(&difficulty).copied().unwrap_or(difficulty.default_wind())
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -189,7 +370,7 @@ where
             inner_map
                 .iter()
                 .map(|(key_str, action_str)| {
-                    let action: T = serde_yaml::from_str(&action_str).unwrap();
+                    let action: T = serde_yaml::from_str(action_str).unwrap();
                     (parse_key_event(key_str).unwrap(), action)
                 })
                 .collect()
@@ -211,11 +392,11 @@ fn merge_keybinding_maps<A: Clone>(
     let mut click: HashMap<KeyEvent, (A, ActionState)> =
         click.into_iter().map(|(event, command)| (event, (command, ActionState::default()))).collect();
     for (mut key_event, action) in hold {
-        click.insert(key_event.clone(), (action.clone(), ActionState::Start));
+        click.insert(key_event, (action.clone(), ActionState::Start));
         key_event.kind = KeyEventKind::Release;
-        click.insert(key_event.clone(), (action.clone(), ActionState::End));
+        click.insert(key_event, (action.clone(), ActionState::End));
         key_event.kind = KeyEventKind::Repeat;
-        click.insert(key_event.clone(), (action.clone(), ActionState::Repeat));
+        click.insert(key_event, (action.clone(), ActionState::Repeat));
     }
 
     click
@@ -332,7 +513,7 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
             char = format!("F({c})");
             &char
         },
-        KeyCode::Char(c) if c == ' ' => "Space",
+        KeyCode::Char(' ') => "Space",
         KeyCode::Char(c) => {
             char = c.to_string();
             &char
@@ -0,0 +1,7 @@
+use std::time::Duration;
+
+pub const FILE: &str = "achievements.json"; // In the data dir
+pub const FIRST_PIPES: u16 = 5;
+pub const GLIDE_SECONDS: f32 = 3.0; // Without flapping
+pub const CHRISTMAS: (u32, u32) = (12, 25); // Month and day
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
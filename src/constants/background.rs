@@ -1,7 +1,16 @@
+use ratatui::style::Color;
+
 pub const GROUND_HEIGHT: u16 = 3;
 
 pub const SNOWFLAKES: [char; 3] = ['❄', '❅', '❆'];
 
+// Snow in wind gusts
+pub const WIND_DRIFT: f32 = 3.0; // Columns per row at a full headwind
+pub const WIND_LIFT: f32 = 2.0; // Change of the snowfall speed at a full updraft: fraction of the speed
+pub const WIND_STREAK_THRESHOLD: f32 = 0.3; // Intensity from which snowflakes are drawn as streaks
+pub const HORIZONTAL_STREAK: char = '─';
+pub const VERTICAL_STREAK: char = '│';
+
 pub const SNOWMAN_MARGIN: u16 = 8;
 pub const SNOWMAN: &str = r#"
             .-~~\
@@ -65,3 +74,59 @@ pub const TREEBODY_TEXT: &str = r#"
 i/ *   O   O   * \i
 /=================\
 "#;
+
+pub struct ParallaxLayer {
+    pub texts: &'static [(&'static str, Color)],
+    pub speed: f32, // Fraction of pipe speed
+}
+
+// Drawn as an opaque blank, so farther layers do not show through
+pub const PARALLAX_FILL: char = '`';
+
+// Ordered from the farthest to the nearest layer
+pub const PARALLAX_LAYERS: [ParallaxLayer; 2] = [
+    ParallaxLayer { texts: &[(MOUNTAIN_TEXT, Color::DarkGray), (MOUNTAIN_SNOW_TEXT, Color::White)], speed: 0.1 },
+    ParallaxLayer { texts: &[(HOUSE_TEXT, Color::Gray), (HOUSE_WINDOW_TEXT, Color::Yellow)], speed: 0.4 },
+];
+pub const GROUND_LAYER: ParallaxLayer = ParallaxLayer { texts: &[(GROUND_TEXT, Color::White)], speed: 1.0 };
+
+pub const MOUNTAIN_TEXT: &str = r#"
+             /\
+            /``\                       /\
+           /````\          /\         /``\
+          /``````\        /``\       /````\      /\
+    /\   /````````\      /````\     /``````\    /``\
+   /``\ /``````````\    /``````\   /````````\  /````\
+__/````V````````````\__/````````\_/``````````\/``````\______
+"#;
+pub const MOUNTAIN_SNOW_TEXT: &str = r#"
+ 
+             ^^
+                                       ^^
+                           ^^
+                                                 ^^
+    ^^
+"#;
+
+pub const HOUSE_TEXT: &str = r#"
+        ____                              ___
+       /\```\          ___               /\``\
+      /``\___\        /\``\     _____   /``\__\   .-.
+      |``|```|       /``\__\   /\````\  |``|``|  (`*`)
+      |``|```|       |``|``|  /``\____\ |``|``|   '|'
+      |``|```|       |``|``|  |``|````| |``|``|    |
+"#;
+pub const HOUSE_WINDOW_TEXT: &str = r#"
+ 
+ 
+ 
+       ■■  ■                             ■■ ■
+                      ■   ■
+           ■                   ■   ■        ■
+"#;
+
+pub const GROUND_TEXT: &str = r#"
+############################################################
+#.#'#,#.###,#'#.#,###'#.#,#.#'###,#.#'#,#.###'#,#.#'#,#.####
+############################################################
+"#;
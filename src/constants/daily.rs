@@ -0,0 +1 @@
+pub const FILE: &str = "daily.json"; // In the data dir
@@ -1,5 +1,7 @@
 use ratatui::style::Color;
 
+use crate::config::WindConfig;
+
 pub const GRAVITY: f32 = 90.0;
 pub const UP_VELOCITY: f32 = 20.0;
 pub const VELOCITY_LIMIT: f32 = 20.0;
@@ -15,7 +17,40 @@ pub const LAST_PIPE_COLOR: Option<Color> = Some(Color::Cyan);
 
 pub const MAX_PIPE_NUM: u16 = 20;
 
+pub const RESUME_COUNTDOWN: f32 = 3.0; // Seconds
+pub const START_COUNTDOWN: f32 = 3.0; // Seconds
+pub const DEAD_DELAY: f32 = 1.0; // Seconds the crashed bird is shown before the next run
+pub const HOVER_AMPLITUDE: f32 = 1.0; // Rows
+pub const HOVER_PERIOD: f32 = 1.2; // Seconds
+pub const PROMPT_OFFSET: i32 = 6; // Rows below the center of the canvas
+
+pub const TUTORIAL_PIPE_SPEED: f32 = 0.6; // Fraction of the pipe speed
+pub const TUTORIAL_FLAPS: u16 = 3;
+pub const TUTORIAL_STEP_DURATION: f32 = 5.0; // Seconds
+pub const CALLOUT_COLOR: Color = Color::Yellow;
+
+pub const WIND_EASY: WindConfig = WindConfig { interval: 12.0, duration: 1.5, lift: 20.0, drag: 0.2 };
+pub const WIND_NORMAL: WindConfig = WindConfig { interval: 8.0, duration: 2.0, lift: 40.0, drag: 0.35 };
+pub const WIND_HARD: WindConfig = WindConfig { interval: 5.0, duration: 2.5, lift: 60.0, drag: 0.5 };
+
 pub const BIRD_INITIAL_X: u16 = 20;
+pub const PLAYER_TINTS: [Option<Color>; 2] = [None, Some(Color::LightRed)]; // Base layer color of each player's bird
+pub const RACE_SPACING: f32 = 8.0; // Rows between the birds before a race
+pub const GHOST_COLOR: Color = Color::DarkGray; // Of the opponent over the network
+pub const GHOST_SMOOTHING: f32 = 0.3; // Fraction of the way to its last known position the ghost moves per update
+pub const BOT_AIM_OFFSET: f32 = 1.5; // Rows below the middle of the gap the bot flaps at
+pub const POSITION_INTERVAL: f32 = 0.05; // Seconds between positions sent to the opponent
+pub const SIM_STEP: f32 = 1.0 / 30.0; // Seconds the headless simulation advances per step
+pub const SIM_SURVIVAL_REWARD: f32 = 0.1; // Per step
+pub const SIM_PIPE_REWARD: f32 = 1.0;
+pub const SIM_CRASH_REWARD: f32 = -1.0;
+pub const REPLAY_MAX_STEPS: u32 = 30 * 60 * 30; // Half an hour of simulation
+pub const REPLAY_MIN_SIZE: (u16, u16) = (40, 20); // Width and height the course can be drawn in
+pub const REPLAY_MAX_SIZE: (u16, u16) = (400, 200);
+pub const ASSIST_EXTRA_GAP: u16 = 4; // Rows
+pub const SAVE_FILE: &str = "run.json"; // In the data dir, the run suspended to be continued
+pub const HEATMAP_COLD: (u8, u8, u8) = (40, 40, 120); // Background of the cells with the fewest crashes
+pub const HEATMAP_HOT: (u8, u8, u8) = (220, 40, 20); // And with the most
 pub const BIRD_TEXTS: [&str; 2] = [
     r#"
  ^ ^
@@ -31,3 +66,123 @@ pub const BIRD_TEXTS: [&str; 2] = [
 "#,
 ];
 pub const BIRD_COLORS: [Option<Color>; 2] = [Some(Color::LightBlue), Some(Color::Yellow)];
+
+pub const BIRD_FLAP_FRAMES: [([&str; 2], f32); 3] = [
+    (
+        [
+            r#"
+\^ ^/
+(   )
+(   )
+- - -
+"#,
+            BIRD_TEXTS[1],
+        ],
+        0.08,
+    ),
+    (
+        [
+            r#"
+ ^ ^
+<   >
+(   )
+- - -
+"#,
+            BIRD_TEXTS[1],
+        ],
+        0.08,
+    ),
+    (
+        [
+            r#"
+ ^ ^
+(   )
+/   \
+- - -
+"#,
+            BIRD_TEXTS[1],
+        ],
+        0.08,
+    ),
+];
+pub const BIRD_FALL_TEXTS: [&str; 2] = [
+    r#"
+ ^ ^
+(   \
+(   )
+ - -
+"#,
+    r#"
+    
+ o,o 
+     
+  " "
+"#,
+];
+pub const BIRD_DEAD_TEXTS: [&str; 2] = [
+    BIRD_TEXTS[0],
+    r#"
+    
+ X,X 
+     
+ " " 
+"#,
+];
+// Falling faster than this tilts the bird
+pub const BIRD_TILT_VELOCITY: f32 = -10.0;
+
+pub const REINDEER_TEXTS: [&str; 3] = [
+    r#"
+Y   Y
+ \_/
+(   )
+ ( )
+"#,
+    r#"
+     
+     
+ o o
+     
+"#,
+    r#"
+     
+     
+     
+  @
+"#,
+];
+pub const REINDEER_COLORS: [Option<Color>; 3] = [Some(Color::Rgb(160, 100, 50)), Some(Color::White), Some(Color::Red)];
+
+pub const SANTA_TEXTS: [&str; 2] = [
+    r#"
+ /\
+/__\
+(   )
+     
+"#,
+    r#"
+     
+    o
+ o o
+\www/
+"#,
+];
+pub const SANTA_COLORS: [Option<Color>; 2] = [Some(Color::Red), Some(Color::White)];
+
+pub const SNOWFLAKE_TEXTS: [&str; 2] = [
+    r#"
+\ | /
+ \|/
+--+--
+ /|\
+/ | \
+"#,
+    r#"
+     
+     
+  ❄
+     
+     
+"#,
+];
+pub const SNOWFLAKE_COLORS: [Option<Color>; 2] = [Some(Color::LightCyan), Some(Color::White)];
@@ -1,2 +1,4 @@
 pub const SNOWFLAKE_DENSITY: f32 = 1.0 / 40.0;
 pub const SNOWFLAKE_SPEED: f32 = 2.0;
+pub const PREVIEW_VPADDING: u16 = 1;
+pub const ATTRACT_DELAY: f32 = 20.0; // Idle seconds before the demo starts
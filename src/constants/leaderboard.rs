@@ -0,0 +1,9 @@
+use std::time::Duration;
+
+pub const DEFAULT_PORT: u16 = 7778;
+pub const FILE: &str = "leaderboard.json"; // In the data dir, unless the server is given another file
+pub const TOP_ENTRIES: usize = 10;
+pub const MAX_TOP_ENTRIES: usize = 100; // Sent by the server for one request
+pub const MAX_NAME_LENGTH: usize = 16;
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+pub const MUTATORS_WIDTH: u16 = 21; // Enough for the labels of all mutators
@@ -1,8 +1,15 @@
-pub mod home;
-pub mod game;
-pub mod title;
+pub mod achievements;
 pub mod background;
 pub mod card;
+pub mod daily;
+pub mod game;
+pub mod home;
+pub mod leaderboard;
+pub mod mutators;
+pub mod speedrun;
+pub mod stats;
+pub mod title;
+pub mod tournament;
 
 pub const WIDTH: u16 = 150;
 pub const HEIGHT: u16 = 45;
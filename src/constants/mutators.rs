@@ -0,0 +1,4 @@
+pub const FAST_PIPES: f32 = 1.5; // Times the pipe speed
+pub const TINY_HITBOX: f32 = 0.5; // Fraction of the size of the bird it can be hit on
+pub const REVEAL_DISTANCE: i16 = 18; // Columns ahead of the bird invisible pipes flash in
+pub const FLASH_COLUMNS: i16 = 3; // Columns an invisible pipe stays shown, then hidden, while flashing
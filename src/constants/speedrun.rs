@@ -0,0 +1,6 @@
+use ratatui::style::Color;
+
+pub const FILE: &str = "splits.json"; // In the data dir
+pub const SPLIT_PIPES: u16 = 5; // Pipes between splits
+pub const AHEAD_COLOR: Color = Color::LightGreen; // Of the delta to the personal best
+pub const BEHIND_COLOR: Color = Color::LightRed;
@@ -0,0 +1,4 @@
+pub const FILE: &str = "stats.json"; // In the data dir
+pub const EXPORT_CSV: &str = "runs.csv"; // Exported to the data dir
+pub const EXPORT_JSON: &str = "runs.json";
+pub const CHART_RUNS: usize = 60; // Most recent runs in the charts
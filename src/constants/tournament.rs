@@ -0,0 +1,4 @@
+pub const ROUNDS: usize = 3;
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 8;
+pub const MAX_NAME_LENGTH: usize = 12;
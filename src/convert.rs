@@ -0,0 +1,139 @@
+use std::{collections::HashMap, path::Path};
+
+use color_eyre::eyre::{eyre, Result};
+use image::{imageops::FilterType, DynamicImage, GenericImageView};
+use ratatui::style::Color;
+
+use crate::skin::Skin;
+
+const PIXEL: char = '█';
+const ALPHA_THRESHOLD: u8 = 128;
+
+// Terminal colors with the RGB values of the default xterm palette
+const PALETTE: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+fn quantize(rgb: [u8; 3]) -> Color {
+    let distance = |palette_rgb: &[u8; 3]| {
+        rgb.iter().zip(palette_rgb.iter()).map(|(a, b)| (*a as i32 - *b as i32).pow(2)).sum::<i32>()
+    };
+    PALETTE.iter().min_by_key(|(_, palette_rgb)| distance(palette_rgb)).map(|(color, _)| *color).unwrap()
+}
+
+/// Size of the sprite in cells. A cell is about twice as tall as it is wide, so a missing height is half of the
+/// height that keeps the aspect ratio.
+pub fn target_size(image_size: (u32, u32), width: Option<u32>, height: Option<u32>) -> (u32, u32) {
+    let (image_width, image_height) = image_size;
+    let scaled_height = |width: u32| (width * image_height / image_width.max(1) / 2).max(1);
+    let scaled_width = |height: u32| (height * 2 * image_width / image_height.max(1)).max(1);
+
+    match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        (Some(width), None) => (width, scaled_height(width)),
+        (None, Some(height)) => (scaled_width(height), height),
+        (None, None) => (image_width, scaled_height(image_width)),
+    }
+}
+
+/// Convert an image into sprite layers, one per terminal color, ordered from the most to the least used color.
+/// Transparent pixels become whitespace, so they are skipped when whitespace is ignored.
+pub fn image_to_layers(image: &DynamicImage, width: u32, height: u32) -> (Vec<String>, Vec<Option<Color>>) {
+    let image = image.resize_exact(width, height, FilterType::Nearest);
+
+    let mut pixels: Vec<Vec<Option<Color>>> = vec![vec![None; width as usize]; height as usize];
+    let mut counts: HashMap<Color, usize> = HashMap::new();
+    for (x, y, rgba) in image.pixels() {
+        let [r, g, b, a] = rgba.0;
+        if a < ALPHA_THRESHOLD {
+            continue;
+        }
+        let color = quantize([r, g, b]);
+        pixels[y as usize][x as usize] = Some(color);
+        *counts.entry(color).or_default() += 1;
+    }
+
+    let mut colors = counts.into_iter().collect::<Vec<_>>();
+    colors.sort_by_key(|(color, count)| (std::cmp::Reverse(*count), PALETTE.iter().position(|(c, _)| c == color)));
+
+    let layers = colors
+        .iter()
+        .map(|(color, _)| {
+            let rows = pixels.iter().map(|row| {
+                let line = row.iter().map(|pixel| if *pixel == Some(*color) { PIXEL } else { ' ' }).collect::<String>();
+                // Keep blank rows, so that the layers stay aligned
+                let line = line.trim_end();
+                if line.is_empty() {
+                    " ".to_string()
+                } else {
+                    line.to_string()
+                }
+            });
+            std::iter::once(String::new()).chain(rows).collect::<Vec<_>>().join("\n")
+        })
+        .collect();
+
+    (layers, colors.into_iter().map(|(color, _)| Some(color)).collect())
+}
+
+pub fn convert_image(path: &Path, width: Option<u32>, height: Option<u32>, name: Option<&str>) -> Result<Skin> {
+    let image = image::open(path)?;
+    let (width, height) = target_size(image.dimensions(), width, height);
+    let (layers, colors) = image_to_layers(&image, width, height);
+    if layers.is_empty() {
+        return Err(eyre!("{} has no opaque pixels", path.display()));
+    }
+
+    let name = match name {
+        Some(name) => name.to_string(),
+        None => path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default(),
+    };
+    let layers = layers.iter().map(|layer| layer.as_str()).collect::<Vec<_>>();
+    Ok(Skin::new(&name, &layers, &colors))
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn test_image_to_layers() {
+        let mut image = RgbaImage::new(3, 2);
+        image.put_pixel(0, 0, Rgba([250, 10, 10, 255]));
+        image.put_pixel(1, 0, Rgba([0, 0, 0, 0]));
+        image.put_pixel(2, 0, Rgba([250, 10, 10, 255]));
+        image.put_pixel(0, 1, Rgba([0, 0, 0, 0]));
+        image.put_pixel(1, 1, Rgba([10, 10, 240, 255]));
+        image.put_pixel(2, 1, Rgba([0, 0, 0, 0]));
+
+        let (layers, colors) = image_to_layers(&DynamicImage::ImageRgba8(image), 3, 2);
+
+        assert_eq!(colors, vec![Some(Color::LightRed), Some(Color::Blue)]);
+        assert_eq!(layers, vec!["\n█ █\n ".to_string(), "\n \n █".to_string()]);
+    }
+
+    #[test]
+    fn test_target_size() {
+        assert_eq!(target_size((16, 16), None, None), (16, 8));
+        assert_eq!(target_size((16, 16), Some(8), None), (8, 4));
+        assert_eq!(target_size((16, 16), None, Some(4)), (8, 4));
+        assert_eq!(target_size((16, 16), Some(5), Some(5)), (5, 5));
+    }
+}
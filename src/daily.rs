@@ -0,0 +1,85 @@
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::daily,
+    utils::{civil_date, load_data, save_data},
+};
+
+/// The course of the challenge of `day`, the same for everyone on that date
+pub fn seed(day: i64) -> u64 {
+    let (year, month, day) = civil_date(day);
+    year as u64 * 10000 + month as u64 * 100 + day as u64
+}
+
+/// Results of the daily challenge, saved to `<data dir>/daily.json`. Days are counted since 1970-01-01.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyRecord {
+    pub last_day: Option<i64>, // Of the last attempt
+    pub last_score: u16,
+    pub best: u16,
+    streak: u32, // Days in a row up to the last attempt
+}
+
+impl DailyRecord {
+    pub fn load() -> Self {
+        load_data(daily::FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data(daily::FILE, self)
+    }
+
+    pub fn played(&self, day: i64) -> bool {
+        self.last_day == Some(day)
+    }
+
+    /// Days in a row played up to `day`, still going if the last attempt was yesterday
+    pub fn streak(&self, day: i64) -> u32 {
+        match self.last_day {
+            Some(last) if last >= day - 1 => self.streak,
+            _ => 0,
+        }
+    }
+
+    /// Spend the attempt of `day`, which scores nothing until it ends
+    pub fn attempt(&mut self, day: i64) {
+        if self.played(day) {
+            return;
+        }
+        self.streak = if self.last_day == Some(day - 1) { self.streak + 1 } else { 1 };
+        self.last_day = Some(day);
+        self.last_score = 0;
+    }
+
+    pub fn score(&mut self, day: i64, score: u16) {
+        if self.played(day) {
+            self.last_score = self.last_score.max(score);
+            self.best = self.best.max(score);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streak() {
+        let mut record = DailyRecord::default();
+        record.attempt(100);
+        record.score(100, 7);
+        record.attempt(101);
+        record.score(101, 3);
+        // A second attempt on the same day changes nothing
+        record.attempt(101);
+        assert_eq!((record.streak(101), record.streak(102), record.last_score, record.best), (2, 2, 3, 7));
+        assert!(record.played(101) && !record.played(102));
+
+        // Skipping a day breaks the streak
+        assert_eq!(record.streak(103), 0);
+        record.attempt(103);
+        assert_eq!(record.streak(103), 1);
+        assert_eq!(seed(20812), 20261225);
+    }
+}
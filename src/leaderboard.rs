@@ -0,0 +1,242 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    time::timeout,
+};
+
+use crate::{
+    config::Difficulty,
+    constants::leaderboard,
+    pages::game::{mutators::Mutators, simulation::Replay},
+};
+
+/// A run sent to the leaderboard. The score only counts if the replay earns it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub name: String,
+    pub score: u16,
+    pub replay: Replay,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub name: String,
+    pub score: u16,
+    pub distance: u32,
+    #[serde(default)]
+    pub mutators: Mutators, // Of the run
+    #[serde(default)]
+    pub difficulty: Difficulty, // Whose wind the run was flown in
+}
+
+/// One line of JSON from a client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Request {
+    Submit { run: Run },
+    Top { limit: usize },
+}
+
+/// One line of JSON from the server, for each request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    Accepted { rank: usize },
+    Rejected { reason: String },
+    Top { entries: Vec<Entry> },
+}
+
+/// Scores kept by the server, best first, saved to a file after every accepted run
+#[derive(Debug)]
+struct Board {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Board {
+    fn load(path: &Path) -> Result<Self> {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Board { path: path.to_path_buf(), entries })
+    }
+
+    /// Rank `entry` of a verified run, and return its rank
+    fn insert(&mut self, entry: Entry) -> Result<usize> {
+        // Earlier runs stay ahead of later runs with the same score
+        let rank = self
+            .entries
+            .iter()
+            .take_while(|other| (other.score, other.distance) >= (entry.score, entry.distance))
+            .count();
+        self.entries.insert(rank, entry);
+        if let Some(directory) = self.path.parent() {
+            std::fs::create_dir_all(directory)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok(rank + 1)
+    }
+
+    fn top(&self, limit: usize) -> Vec<Entry> {
+        self.entries.iter().take(limit.min(leaderboard::MAX_TOP_ENTRIES)).cloned().collect()
+    }
+}
+
+/// Fly the replay of `run` again, and return its entry if it earns the score it claims in the wind of a difficulty
+fn verify(run: Run) -> Result<Entry> {
+    let name = run.name.trim().chars().take(leaderboard::MAX_NAME_LENGTH).collect::<String>();
+    if name.is_empty() {
+        return Err(eyre!("A name is required"));
+    }
+    let difficulty =
+        Difficulty::of_wind(run.replay.wind).ok_or_else(|| eyre!("The wind is not the one of a difficulty"))?;
+    let (score, distance) = run.replay.play()?;
+    if score != run.score {
+        return Err(eyre!("The replay passes {score} pipes, not {}", run.score));
+    }
+    Ok(Entry { name, score, distance, mutators: run.replay.mutators, difficulty })
+}
+
+async fn respond(line: &str, board: &Mutex<Board>) -> Response {
+    match serde_json::from_str::<Request>(line) {
+        Ok(Request::Submit { run }) => {
+            // Replays take a while, so they are flown off the runtime and without holding the board
+            let entry = match tokio::task::spawn_blocking(move || verify(run)).await {
+                Ok(entry) => entry,
+                Err(e) => Err(e.into()),
+            };
+            match entry.and_then(|entry| board.lock().unwrap().insert(entry)) {
+                Ok(rank) => Response::Accepted { rank },
+                Err(e) => Response::Rejected { reason: e.to_string() },
+            }
+        },
+        Ok(Request::Top { limit }) => Response::Top { entries: board.lock().unwrap().top(limit) },
+        Err(e) => Response::Rejected { reason: e.to_string() },
+    }
+}
+
+/// Keep the scores in `path`, and answer clients on `port` until stopped
+pub async fn serve(port: u16, path: &Path) -> Result<()> {
+    let board = Arc::new(Mutex::new(Board::load(path)?));
+    let listener = TcpListener::bind(("0.0.0.0", port)).await?;
+    println!("Leaderboard listening on port {port}, scores in {}", path.display());
+    loop {
+        let (stream, address) = listener.accept().await?;
+        let board = board.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, &board).await {
+                log::warn!("Leaderboard client {address}: {e}");
+            }
+        });
+    }
+}
+
+async fn handle(stream: TcpStream, board: &Mutex<Board>) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(line) = timeout(leaderboard::REQUEST_TIMEOUT, lines.next_line()).await?? {
+        let response = respond(&line, board).await;
+        writer.write_all((serde_json::to_string(&response)? + "\n").as_bytes()).await?;
+    }
+    Ok(())
+}
+
+async fn request(address: &str, request: &Request) -> Result<Response> {
+    let address =
+        if address.contains(':') { address.to_string() } else { format!("{address}:{}", leaderboard::DEFAULT_PORT) };
+    let exchange = async {
+        let stream = TcpStream::connect(&address).await?;
+        let (reader, mut writer) = stream.into_split();
+        writer.write_all((serde_json::to_string(request)? + "\n").as_bytes()).await?;
+        let line = BufReader::new(reader).lines().next_line().await?;
+        let line = line.ok_or_else(|| eyre!("The leaderboard closed the connection"))?;
+        Ok(serde_json::from_str(&line)?)
+    };
+    timeout(leaderboard::REQUEST_TIMEOUT, exchange).await.map_err(|_| eyre!("The leaderboard did not answer"))?
+}
+
+/// Send a run to the leaderboard at `address`, and return its rank
+pub async fn submit(address: &str, run: Run) -> Result<usize> {
+    match request(address, &Request::Submit { run }).await? {
+        Response::Accepted { rank } => Ok(rank),
+        Response::Rejected { reason } => Err(eyre!(reason)),
+        Response::Top { .. } => Err(eyre!("Unexpected answer from the leaderboard")),
+    }
+}
+
+/// The best runs on the leaderboard at `address`
+pub async fn top(address: &str, limit: usize) -> Result<Vec<Entry>> {
+    match request(address, &Request::Top { limit }).await? {
+        Response::Top { entries } => Ok(entries),
+        Response::Rejected { reason } => Err(eyre!(reason)),
+        Response::Accepted { .. } => Err(eyre!("Unexpected answer from the leaderboard")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::WindConfig,
+        constants::{game, HEIGHT, WIDTH},
+    };
+
+    #[tokio::test]
+    async fn test_forged_runs_are_rejected() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("leaderboard-{}.json", std::process::id()));
+        let board = Mutex::new(Board { path: path.clone(), entries: Vec::new() });
+        let replay = Replay::new(7, WIDTH, HEIGHT).wind(game::WIND_HARD);
+        let (score, distance) = replay.play()?;
+
+        let forged = Run { name: "Eve".to_string(), score: score + 5, replay: replay.clone() };
+        assert!(matches!(
+            respond(&serde_json::to_string(&Request::Submit { run: forged })?, &board).await,
+            Response::Rejected { .. }
+        ));
+        let ann = Run { name: "Ann".to_string(), score, replay: replay.clone() };
+        assert_eq!(respond(&serde_json::to_string(&Request::Submit { run: ann })?, &board).await, Response::Accepted {
+            rank: 1
+        });
+        let mut board = board.into_inner().unwrap();
+        assert_eq!(board.insert(verify(Run { name: "Bob".to_string(), score, replay })?)?, 2);
+
+        let saved = Board::load(&path)?;
+        std::fs::remove_file(&path)?;
+        assert_eq!(saved.top(1), vec![Entry {
+            name: "Ann".to_string(),
+            score,
+            distance,
+            mutators: Mutators::default(),
+            difficulty: Difficulty::Hard,
+        }]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_forged_wind_is_rejected() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("leaderboard-wind-{}.json", std::process::id()));
+        let board = Mutex::new(Board { path, entries: Vec::new() });
+        let calm = Replay::new(7, WIDTH, HEIGHT);
+        let gentle = calm.clone().wind(WindConfig { interval: 0.0, ..game::WIND_HARD });
+
+        for replay in [calm, gentle] {
+            let (score, _) = replay.play()?;
+            let run = Run { name: "Eve".to_string(), score, replay };
+            assert!(matches!(
+                respond(&serde_json::to_string(&Request::Submit { run })?, &board).await,
+                Response::Rejected { .. }
+            ));
+        }
+        assert!(board.into_inner().unwrap().entries.is_empty());
+        Ok(())
+    }
+}
@@ -2,12 +2,21 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
 
+mod achievements;
 mod action;
 mod app;
+mod assets;
+mod cli;
 mod components;
 mod config;
 mod constants;
+mod convert;
+mod daily;
+mod leaderboard;
+mod net;
 mod pages;
+mod skin;
+mod stats;
 mod tui;
 mod utils;
 
@@ -23,7 +32,17 @@ async fn tokio_main() -> Result<()> {
 
     initialize_panic_handler()?;
 
-    let mut app = App::new(60.0, 60.0)?;
+    let matches = cli::cli().get_matches();
+    let session = match matches.subcommand() {
+        Some(("convert", args)) => return cli::convert(args),
+        Some(("sim", args)) => return cli::sim(args),
+        Some(("leaderboard", args)) => return cli::leaderboard(args).await,
+        Some(("host", args)) => Some(cli::host(args)?),
+        Some(("join", args)) => Some(cli::join(args)),
+        _ => None,
+    };
+
+    let mut app = App::new(60.0, 60.0)?.session(session);
     app.run().await?;
 
     Ok(())
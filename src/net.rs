@@ -0,0 +1,245 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{tcp::OwnedWriteHalf, TcpListener, TcpStream},
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    time::{interval, sleep_until, Instant},
+};
+
+use crate::config::WindConfig;
+
+/// Bumped on every incompatible change of `Message`
+pub const PROTOCOL_VERSION: u32 = 2;
+pub const DEFAULT_PORT: u16 = 7777;
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+const PEER_TIMEOUT: Duration = Duration::from_secs(5); // Without any message from the peer
+
+/// One line of JSON on the wire
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    Hello { version: u32 },
+    Start { seed: u64, wind: WindConfig }, // From the host, once both sides said hello
+    Position { y: f32, score: u16, distance: u32 },
+    Over { score: u16, distance: u32, crashed: bool },
+    Ping,
+    Bye,
+}
+
+impl Message {
+    fn encode(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)? + "\n")
+    }
+
+    fn decode(line: &str) -> Result<Self> {
+        Ok(serde_json::from_str(line)?)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Status {
+    #[default]
+    Connecting,
+    Connected,
+    Disconnected(String), // Reason
+}
+
+/// What is known about the other side, updated as messages arrive
+#[derive(Debug, Clone, Default)]
+pub struct Peer {
+    pub status: Status,
+    pub seed: Option<u64>,        // Seed of the course both sides fly
+    pub wind: Option<WindConfig>, // Of the host, which both sides fly in
+    pub y: Option<f32>,
+    pub score: u16,
+    pub distance: u32,
+    pub over: Option<bool>, // Whether the peer crashed, once its run is over
+}
+
+#[derive(Debug, Clone)]
+enum Role {
+    Host(u16, WindConfig),
+    Join(String),
+}
+
+/// A versus match with one other instance over TCP
+#[derive(Debug, Clone)]
+pub struct Session {
+    role: Role,
+    outgoing: UnboundedSender<Message>,
+    peer: Arc<Mutex<Peer>>,
+}
+
+impl Session {
+    /// Wait for another instance to join on `port`, and fly the match in `wind`
+    pub fn host(port: u16, wind: WindConfig) -> Self {
+        Self::spawn(Role::Host(port, wind))
+    }
+
+    /// Join the instance hosting at `address`, on the default port if none is given
+    pub fn join(address: &str) -> Self {
+        let address = if address.contains(':') { address.to_string() } else { format!("{address}:{DEFAULT_PORT}") };
+        Self::spawn(Role::Join(address))
+    }
+
+    fn spawn(role: Role) -> Self {
+        let (outgoing, outgoing_rx) = mpsc::unbounded_channel();
+        let peer = Arc::new(Mutex::new(Peer::default()));
+        let session = Session { role: role.clone(), outgoing, peer: peer.clone() };
+        tokio::spawn(async move {
+            let reason = match run(role, outgoing_rx, peer.clone()).await {
+                Ok(reason) => reason.to_string(),
+                Err(e) => e.to_string(),
+            };
+            log::info!("Versus session ended: {reason}");
+            peer.lock().unwrap().status = Status::Disconnected(reason);
+        });
+        session
+    }
+
+    pub fn is_host(&self) -> bool {
+        matches!(self.role, Role::Host(..))
+    }
+
+    /// Where the session waits or connects, for display
+    pub fn address(&self) -> String {
+        match &self.role {
+            Role::Host(port, _) => format!("port {port}"),
+            Role::Join(address) => address.clone(),
+        }
+    }
+
+    /// Messages are dropped once the connection is gone
+    pub fn send(&self, message: Message) {
+        let _ = self.outgoing.send(message);
+    }
+
+    pub fn peer(&self) -> Peer {
+        self.peer.lock().unwrap().clone()
+    }
+}
+
+/// Exchange messages until either side leaves, and return why the session ended
+async fn run(role: Role, mut outgoing_rx: UnboundedReceiver<Message>, peer: Arc<Mutex<Peer>>) -> Result<&'static str> {
+    let stream = match &role {
+        Role::Host(port, _) => TcpListener::bind(("0.0.0.0", *port)).await?.accept().await?.0,
+        Role::Join(address) => TcpStream::connect(address).await?,
+    };
+    stream.set_nodelay(true)?;
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    write(&mut writer, &Message::Hello { version: PROTOCOL_VERSION }).await?;
+
+    let mut ping = interval(PING_INTERVAL);
+    // Only lines from the peer push the deadline back, not the messages sent to it
+    let mut deadline = Instant::now() + PEER_TIMEOUT;
+    loop {
+        tokio::select! {
+            line = lines.next_line() => {
+                deadline = Instant::now() + PEER_TIMEOUT;
+                let Some(line) = line? else {
+                    return Ok("The opponent left");
+                };
+                let message = Message::decode(&line)?;
+                if let Some(reply) = receive(&role, message, &peer)? {
+                    write(&mut writer, &reply).await?;
+                }
+                if peer.lock().unwrap().status != Status::Connected {
+                    return Ok("The opponent left");
+                }
+            },
+            message = outgoing_rx.recv() => {
+                // The session is dropped when the game leaves the match
+                let message = message.unwrap_or(Message::Bye);
+                write(&mut writer, &message).await?;
+                if message == Message::Bye {
+                    return Ok("Left the match");
+                }
+            },
+            _ = ping.tick() => write(&mut writer, &Message::Ping).await?,
+            _ = sleep_until(deadline) => return Err(eyre!("The opponent timed out")),
+        }
+    }
+}
+
+async fn write(writer: &mut OwnedWriteHalf, message: &Message) -> Result<()> {
+    writer.write_all(message.encode()?.as_bytes()).await?;
+    Ok(())
+}
+
+/// Apply a message from the peer, and return the reply if one is due
+fn receive(role: &Role, message: Message, peer: &Mutex<Peer>) -> Result<Option<Message>> {
+    let mut peer = peer.lock().unwrap();
+    let reply = match message {
+        Message::Hello { version } if version != PROTOCOL_VERSION => {
+            return Err(eyre!("The opponent speaks protocol version {version}, this is version {PROTOCOL_VERSION}"));
+        },
+        Message::Hello { .. } => {
+            peer.status = Status::Connected;
+            match role {
+                Role::Host(_, wind) => {
+                    let seed = rand::random();
+                    (peer.seed, peer.wind) = (Some(seed), Some(*wind));
+                    Some(Message::Start { seed, wind: *wind })
+                },
+                Role::Join(_) => None,
+            }
+        },
+        Message::Start { seed, wind } => {
+            (peer.seed, peer.wind) = (Some(seed), Some(wind));
+            None
+        },
+        Message::Position { y, score, distance } => {
+            peer.y = Some(y);
+            peer.score = score;
+            peer.distance = distance;
+            None
+        },
+        Message::Over { score, distance, crashed } => {
+            peer.score = score;
+            peer.distance = distance;
+            peer.over = Some(crashed);
+            None
+        },
+        Message::Ping => None,
+        Message::Bye => {
+            peer.status = Status::Disconnected("The opponent left".to_string());
+            None
+        },
+    };
+    Ok(reply)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::game;
+
+    #[test]
+    fn test_messages() -> Result<()> {
+        let message = Message::Over { score: 7, distance: 300, crashed: true };
+        let line = message.encode()?;
+        assert_eq!(line, "{\"type\":\"over\",\"score\":7,\"distance\":300,\"crashed\":true}\n");
+        assert_eq!(Message::decode(line.trim())?, message);
+
+        let peer = Mutex::new(Peer::default());
+        assert!(receive(&Role::Join(String::new()), Message::Hello { version: PROTOCOL_VERSION + 1 }, &peer).is_err());
+        let host = Role::Host(DEFAULT_PORT, game::WIND_HARD);
+        let reply = receive(&host, Message::Hello { version: PROTOCOL_VERSION }, &peer)?;
+        let seed = peer.lock().unwrap().seed.unwrap();
+        assert_eq!(reply, Some(Message::Start { seed, wind: game::WIND_HARD }));
+
+        // The joining side flies the wind of the host, whatever its own difficulty
+        let joined = Mutex::new(Peer::default());
+        receive(&Role::Join(String::new()), reply.unwrap(), &joined)?;
+        let joined = joined.into_inner().unwrap();
+        assert_eq!((joined.seed, joined.wind), (Some(seed), Some(game::WIND_HARD)));
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Frame, Page, PageId};
+use crate::{
+    achievements::{Unlocked, ACHIEVEMENTS},
+    action::{act, AchievementsAction, Action, ActionState, Command},
+    config::PageKeyBindings,
+};
+
+pub struct AchievementsPage {
+    pub action_tx: Option<UnboundedSender<Action>>,
+    pub keymap: PageKeyBindings,
+    unlocked: Unlocked,
+}
+
+impl AchievementsPage {
+    pub fn new() -> Self {
+        AchievementsPage { action_tx: None, keymap: PageKeyBindings::default(), unlocked: Unlocked::default() }
+    }
+}
+
+impl Page for AchievementsPage {
+    fn id(&self) -> PageId {
+        PageId::Achievements
+    }
+
+    fn register_keymap(&mut self, keymaps: &HashMap<PageId, PageKeyBindings>) -> Result<()> {
+        if let Some(keymap) = keymaps.get(&self.id()) {
+            self.keymap = keymap.clone();
+        }
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action.command {
+            // Achievements are unlocked in the game, and saved right away
+            Command::OpenAchievements => self.unlocked = Unlocked::load(),
+            Command::Achievements(AchievementsAction::Back) => {
+                if let Some(action_tx) = &self.action_tx {
+                    action_tx.send(act!(Command::ShowHome))?;
+                }
+            },
+            _ => {},
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let mut lines = Vec::new();
+        for achievement in ACHIEVEMENTS {
+            let (mark, style, date) = match self.unlocked.0.get(&achievement) {
+                Some((year, month, day)) => {
+                    ("★", Style::default().fg(Color::Yellow).bold(), format!("  {year}-{month:02}-{day:02}"))
+                },
+                None => ("☆", Style::default().fg(Color::DarkGray), String::new()),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{mark} {}", achievement.title()), style),
+                Span::raw(date).dark_gray(),
+            ]));
+            lines.push(Line::from(format!("  {}", achievement.description())).dark_gray());
+            lines.push(Line::default());
+        }
+        lines.push(Line::from(format!("{} of {} unlocked", self.unlocked.0.len(), ACHIEVEMENTS.len())));
+
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 6;
+        let height = lines.len() as u16 + 4;
+        let [area] = Layout::horizontal([Constraint::Length(width)]).flex(layout::Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)]).flex(layout::Flex::Center).areas(area);
+        let block = Block::default()
+            .title("Achievements")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Cyan))
+            .padding(Padding::symmetric(2, 1))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+        Ok(())
+    }
+
+    fn pause(&mut self) {
+    }
+
+    fn resume(&mut self) {
+    }
+}
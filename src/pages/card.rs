@@ -12,12 +12,13 @@ use tui_input::{backend::crossterm::EventHandler, Input};
 use super::{Frame, Page, PageId};
 use crate::{
     action::{act, Action, ActionState, Command, HomeAction},
+    assets,
     components::{
         background::{Background, BackgroundState},
         multiline::MultiLine,
     },
     config::{key_event_to_string, PageKeyBindings},
-    constants::{card, title},
+    constants::card,
 };
 
 pub struct CardPage {
@@ -40,7 +41,7 @@ impl CardPage {
         let width = lines.iter().map(|s| s.chars().count() * 2).max().unwrap_or(0) as u16 + card::CARD_HPADDING * 2;
         let height = lines.len() as u16 + card::CARD_VPADDING * 2;
 
-        let lines = lines.into_iter().map(|s| Line::from(s)).collect::<Vec<_>>();
+        let lines = lines.into_iter().map(Line::from).collect::<Vec<_>>();
         let [_, area, _] =
             Layout::horizontal(vec![Constraint::Fill(1), Constraint::Length(width), Constraint::Fill(1)]).areas(area);
         let block = Block::default().borders(Borders::ALL).border_type(BorderType::Rounded);
@@ -87,20 +88,20 @@ impl Page for CardPage {
                         action_tx.send(act!(Command::StartGame))?;
                     }
                 },
+                _ => {},
             }
         }
         Ok(None)
     }
 
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
-        let title_lines: Vec<&str> = card::CONGRAT_TEXT.lines().filter(|s| s.len() != 0).collect();
-        let shadow_lines: Vec<&str> = card::CONGRAT_SHADOW.lines().filter(|s| s.len() != 0).collect();
-        let num_title_lines = title_lines.len().max(shadow_lines.len()) as u16;
+        let title = assets::get(assets::CONGRAT);
+        let (width, num_title_lines) = title.size();
 
         // let num_options = self.options.len() as u16;
         // let option_height = num_options * 2 - 1;
-        let card_lines: Vec<&str> = card::CARD_TEXT.lines().filter(|s| s.len() != 0).collect();
-        let num_card_lines = card_lines.len().max(shadow_lines.len()) as u16 + card::CARD_VPADDING * 2;
+        let card_lines: Vec<&str> = card::CARD_TEXT.lines().filter(|s| !s.is_empty()).collect();
+        let num_card_lines = card_lines.len().max(num_title_lines as usize) as u16 + card::CARD_VPADDING * 2;
 
         let [title_area, card_area] =
             Layout::vertical(vec![Constraint::Length(num_title_lines), Constraint::Length(num_card_lines)])
@@ -108,22 +109,18 @@ impl Page for CardPage {
                 .areas(area);
 
         // Draw title
-        let title_width = title_lines.iter().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
-        let shadow_width = shadow_lines.iter().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
-        let width = title_width.max(shadow_width);
-
         let [_, title_area, _] =
             Layout::horizontal(vec![Constraint::Fill(1), Constraint::Length(width), Constraint::Fill(1)])
                 .flex(layout::Flex::SpaceAround)
                 .areas(title_area);
-
-        let shadow_lines =
-            MultiLine::new(shadow_lines).ignore_whitespace(true).pixel_mode().style(Style::default().fg(Color::Green));
-        f.render_widget(shadow_lines, title_area);
-
-        let title_lines =
-            MultiLine::new(title_lines).ignore_whitespace(true).pixel_mode().style(Style::default().fg(Color::Red));
-        f.render_widget(title_lines, title_area);
+        let title_area = title_area.offset(title.offset());
+        for (index, lines) in title.lines().into_iter().enumerate() {
+            let lines = MultiLine::new(lines)
+                .ignore_whitespace(true)
+                .pixel_mode()
+                .style(Style::default().fg(title.color(index)));
+            f.render_widget(lines, title_area);
+        }
 
         // Draw card
         self.draw_card(f, card_area, card_lines)?;
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimationState {
+    Flap,
+    Glide,
+    Fall,
+    Dead,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpriteFrame {
+    pub layers: Vec<Vec<String>>,
+    pub duration: f32, // Seconds
+}
+
+#[derive(Debug, Clone)]
+pub struct Clip {
+    frames: Vec<SpriteFrame>,
+    looping: bool,
+}
+
+impl Clip {
+    pub fn new(frames: Vec<(Vec<&str>, f32)>, looping: bool) -> Self {
+        let frames = frames
+            .into_iter()
+            .map(|(layers, duration)| {
+                SpriteFrame {
+                    layers: layers
+                        .into_iter()
+                        .map(|layer| {
+                            layer.lines().filter(|line| !line.is_empty()).map(|line| line.to_string()).collect()
+                        })
+                        .collect(),
+                    duration,
+                }
+            })
+            .collect();
+        Clip { frames, looping }
+    }
+
+    pub fn still(layers: Vec<&str>) -> Self {
+        Clip::new(vec![(layers, f32::INFINITY)], true)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Animator {
+    clips: HashMap<AnimationState, Clip>,
+    state: AnimationState,
+    frame: usize,
+    elapsed: f32,
+    finished: bool,
+}
+
+impl Animator {
+    pub fn new(clips: HashMap<AnimationState, Clip>, state: AnimationState) -> Self {
+        Animator { clips, state, frame: 0, elapsed: 0.0, finished: false }
+    }
+
+    pub fn state(&self) -> AnimationState {
+        self.state
+    }
+
+    /// Whether a non-looping clip has shown its last frame for its whole duration
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Start the clip of `state` from its first frame, even if it is already playing
+    pub fn play(&mut self, state: AnimationState) {
+        self.state = state;
+        self.frame = 0;
+        self.elapsed = 0.0;
+        self.finished = false;
+    }
+
+    /// Switch to the clip of `state` unless it is already playing
+    pub fn transition(&mut self, state: AnimationState) {
+        if self.state != state {
+            self.play(state);
+        }
+    }
+
+    pub fn advance(&mut self, dt: f32) {
+        let Some(clip) = self.clips.get(&self.state) else {
+            return;
+        };
+        if self.finished || clip.frames.is_empty() {
+            return;
+        }
+
+        self.elapsed += dt;
+        while clip.frames[self.frame].duration > 0.0 && self.elapsed >= clip.frames[self.frame].duration {
+            self.elapsed -= clip.frames[self.frame].duration;
+            if self.frame + 1 < clip.frames.len() {
+                self.frame += 1;
+            } else if clip.looping {
+                self.frame = 0;
+            } else {
+                self.finished = true;
+                break;
+            }
+        }
+    }
+
+    pub fn current_frame(&self) -> Option<&SpriteFrame> {
+        self.clips.get(&self.state).and_then(|clip| clip.frames.get(self.frame))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn animator() -> Animator {
+        let clips = HashMap::from([
+            (AnimationState::Flap, Clip::new(vec![(vec!["a"], 0.1), (vec!["b"], 0.1)], false)),
+            (AnimationState::Glide, Clip::new(vec![(vec!["c"], 0.1), (vec!["d"], 0.1)], true)),
+        ]);
+        Animator::new(clips, AnimationState::Glide)
+    }
+
+    fn current(animator: &Animator) -> String {
+        animator.current_frame().unwrap().layers[0][0].clone()
+    }
+
+    #[test]
+    fn test_looping_clip() {
+        let mut animator = animator();
+        animator.advance(0.15);
+        assert_eq!(current(&animator), "d");
+        animator.advance(0.1);
+        assert_eq!(current(&animator), "c");
+        assert!(!animator.finished());
+    }
+
+    #[test]
+    fn test_clip_finishes_on_last_frame() {
+        let mut animator = animator();
+        animator.play(AnimationState::Flap);
+        animator.advance(0.5);
+        assert_eq!(current(&animator), "b");
+        assert!(animator.finished());
+
+        animator.transition(AnimationState::Flap);
+        assert!(animator.finished());
+        animator.play(AnimationState::Flap);
+        assert_eq!(current(&animator), "a");
+    }
+}
@@ -1,10 +1,18 @@
-use std::time::SystemTime;
+use std::collections::HashMap;
 
 use ratatui::style::Color;
-
-use crate::pages::game::object::Object;
-
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::game,
+    pages::game::{
+        animation::{AnimationState, Animator, Clip},
+        object::Object,
+    },
+};
+
+/// Saved without its animations, which start over when it is loaded
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bird {
     width: u16,
     height: u16,
@@ -12,10 +20,12 @@ pub struct Bird {
     y: f32,
     layers: Vec<Vec<String>>,
     colors: Vec<Option<Color>>,
+    #[serde(skip)]
+    animator: Option<Animator>,
     velocity: f32,
     velocity_limit: f32,
-    last_time: SystemTime,
     paused: bool,
+    hitbox: f32, // Fraction of the size it can be hit on
 }
 
 impl Bird {
@@ -26,8 +36,11 @@ impl Bird {
             .collect();
 
         let height = layers.iter().map(|layer| layer.len()).max().unwrap_or(0) as u16;
-        let width =
-            layers.iter().map(|layer| layer.iter().map(|line| line.len()).max().unwrap_or(0)).max().unwrap_or(0) as u16;
+        let width = layers
+            .iter()
+            .map(|layer| layer.iter().map(|line| line.chars().count()).max().unwrap_or(0))
+            .max()
+            .unwrap_or(0) as u16;
         Bird {
             width,
             height,
@@ -35,28 +48,30 @@ impl Bird {
             y: y as f32,
             layers,
             colors,
+            animator: None,
             velocity: 0.0,
             velocity_limit,
-            last_time: SystemTime::now(),
             paused: false,
+            hitbox: 1.0,
         }
     }
 
-    pub fn reset_time(&mut self) {
-        self.last_time = SystemTime::now();
+    pub fn animations(self, clips: HashMap<AnimationState, Clip>) -> Self {
+        Bird { animator: Some(Animator::new(clips, AnimationState::Glide)), ..self }
     }
 
-    fn get_delta_time(&mut self, now: SystemTime) -> f32 {
-        let dt = now.duration_since(self.last_time).unwrap().as_secs_f32();
-        dt
+    /// Only the middle `scale` of the bird can be hit
+    pub fn hitbox(self, scale: f32) -> Self {
+        Bird { hitbox: scale, ..self }
     }
 
-    pub fn update(&mut self, gravity: f32) {
-        if !self.paused {
-            let now = SystemTime::now();
-            let dt = self.get_delta_time(now);
-            self.last_time = now;
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
 
+    /// Advance the bird by `dt` seconds
+    pub fn step(&mut self, gravity: f32, dt: f32) {
+        if !self.paused {
             self.velocity -= gravity * dt;
             if self.velocity > self.velocity_limit {
                 self.velocity = self.velocity_limit;
@@ -66,11 +81,72 @@ impl Bird {
             }
 
             self.y += self.velocity * dt;
+
+            self.animate(dt);
+        }
+    }
+
+    fn animate(&mut self, dt: f32) {
+        let velocity = self.velocity;
+        if let Some(animator) = self.animator.as_mut() {
+            animator.advance(dt);
+
+            let flapping = animator.state() == AnimationState::Flap && !animator.finished();
+            if animator.state() != AnimationState::Dead && !flapping {
+                if velocity < game::BIRD_TILT_VELOCITY {
+                    animator.transition(AnimationState::Fall);
+                } else {
+                    animator.transition(AnimationState::Glide);
+                }
+            }
+        }
+    }
+
+    /// Fly on from where `saved` was, e.g. a bird loaded from disk
+    pub fn restore(&mut self, saved: &Bird) {
+        self.x = saved.x;
+        self.y = saved.y;
+        self.velocity = saved.velocity;
+    }
+
+    /// Take the look of `other`, e.g. in a new skin, and fly on as before
+    pub fn wear(&mut self, other: Bird) {
+        let state = self.animator.as_ref().map(|animator| animator.state());
+        self.width = other.width;
+        self.height = other.height;
+        self.layers = other.layers;
+        self.colors = other.colors;
+        self.animator = other.animator;
+        if let (Some(animator), Some(state)) = (self.animator.as_mut(), state) {
+            animator.transition(state);
         }
     }
 
+    /// Hold the bird at `y` before the run starts
+    pub fn hover(&mut self, y: f32) {
+        self.y = y;
+        self.velocity = 0.0;
+    }
+
     pub fn up(&mut self, velocity: f32) {
         self.velocity = velocity.min(self.velocity_limit);
+        if let Some(animator) = self.animator.as_mut() {
+            if animator.state() != AnimationState::Dead {
+                animator.play(AnimationState::Flap);
+            }
+        }
+    }
+
+    pub fn die(&mut self) {
+        if let Some(animator) = self.animator.as_mut() {
+            animator.play(AnimationState::Dead);
+        }
+    }
+
+    pub fn revive(&mut self) {
+        if let Some(animator) = self.animator.as_mut() {
+            animator.play(AnimationState::Glide);
+        }
     }
 
     pub fn pause(&mut self) {
@@ -78,7 +154,6 @@ impl Bird {
     }
 
     pub fn resume(&mut self) {
-        self.reset_time();
         self.paused = false;
     }
 }
@@ -104,4 +179,16 @@ impl Object for Bird {
     fn get_colors(&self) -> Vec<Option<Color>> {
         self.colors.clone()
     }
+
+    fn get_animator(&self) -> Option<&Animator> {
+        self.animator.as_ref()
+    }
+
+    fn get_hitbox(&self) -> ((f32, f32), (u16, u16)) {
+        let scale = |size: u16| ((size as f32 * self.hitbox).ceil() as u16).min(size);
+        let (width, height) = (scale(self.width), scale(self.height));
+        let x = self.x + (self.width - width) as f32 / 2.0;
+        let y = self.y + (self.height - height) as f32 / 2.0;
+        ((x, y), (width, height))
+    }
 }
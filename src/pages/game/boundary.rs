@@ -1,10 +1,11 @@
 use std::time::SystemTime;
 
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 
 use crate::pages::game::object::Object;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Boundary {
     width: u16,
     height: u16,
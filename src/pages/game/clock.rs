@@ -0,0 +1,34 @@
+use std::time::SystemTime;
+
+/// Seconds into a run, leaving out pauses
+#[derive(Debug)]
+pub struct Clock {
+    time: f32,
+    last_time: SystemTime,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Clock { time: 0.0, last_time: SystemTime::now() }
+    }
+
+    /// Carry on a run that was `time` seconds in
+    pub fn at(time: f32) -> Self {
+        Clock { time, ..Clock::new() }
+    }
+
+    /// Leave out the time since the last update, after a pause
+    pub fn reset_time(&mut self) {
+        self.last_time = SystemTime::now();
+    }
+
+    pub fn update(&mut self) {
+        let now = SystemTime::now();
+        self.time += now.duration_since(self.last_time).unwrap_or_default().as_secs_f32();
+        self.last_time = now;
+    }
+
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+}
@@ -0,0 +1,75 @@
+use std::collections::VecDeque;
+
+use crate::{
+    constants::game,
+    pages::game::{bird::Bird, boundary::Boundary, object::Object},
+};
+
+/// What a controller sees of the game
+pub struct View<'a> {
+    pub bird: &'a Bird,
+    pub pipes: &'a VecDeque<(Boundary, Boundary)>, // Lower and upper pipe
+    pub height: u16,                               // Of the canvas
+}
+
+/// Flies the bird of the first player
+pub trait Controller {
+    /// Whether the bird should flap now
+    fn update(&mut self, view: &View) -> bool;
+
+    /// Whether the bird follows the key bindings instead
+    fn human(&self) -> bool {
+        false
+    }
+}
+
+pub struct Human;
+
+impl Controller for Human {
+    fn update(&mut self, view: &View) -> bool {
+        false
+    }
+
+    fn human(&self) -> bool {
+        true
+    }
+}
+
+/// Aims at the middle of the gap of the next pipe, flapping whenever the bird sinks below it
+pub struct Bot;
+
+impl Controller for Bot {
+    fn update(&mut self, view: &View) -> bool {
+        let (x, y) = view.bird.get_pos();
+        let (_, height) = view.bird.get_size();
+        let center = y + height as f32 / 2.0;
+
+        let target = view
+            .pipes
+            .iter()
+            .find(|(lower, _)| lower.get_pos().0 + lower.get_size().0 as f32 >= x)
+            .map(|(lower, upper)| (lower.get_pos().1 + lower.get_size().1 as f32 + upper.get_pos().1) / 2.0)
+            .unwrap_or(view.height as f32 / 2.0);
+
+        view.bird.velocity() <= 0.0 && center < target - game::BOT_AIM_OFFSET
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bot_aims_at_gap() {
+        // A gap from row 10 to row 20
+        let lower = Boundary::new(vec![&["||"; 10].join("\n")], vec![None], 30, 0);
+        let upper = Boundary::new(vec![&["||"; 10].join("\n")], vec![None], 30, 20);
+        let pipes = VecDeque::from([(lower, upper)]);
+
+        let low = Bird::new(vec!["o"], vec![None], 20, 5, game::VELOCITY_LIMIT);
+        let high = Bird::new(vec!["o"], vec![None], 20, 17, game::VELOCITY_LIMIT);
+        assert!(Bot.update(&View { bird: &low, pipes: &pipes, height: 40 }));
+        assert!(!Bot.update(&View { bird: &high, pipes: &pipes, height: 40 }));
+        assert!(!Human.update(&View { bird: &low, pipes: &pipes, height: 40 }));
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::game,
+    pages::game::{bird::Bird, boundary::Boundary, object::Object},
+};
+
+/// What the bird crashed into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeathCause {
+    Floor,
+    Ceiling,
+    Pipe,
+}
+
+/// Where the bird crashed, relative to the pipe ahead of it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DeathSpot {
+    pub pipe: u16, // Index of the pipe on the course, from 0
+    pub x: i16,    // Columns from the left edge of the pipe
+    pub y: i16,    // Rows above the bottom of the gap
+}
+
+/// The floor, the ceiling and the pipes drawn from a seed, one column at a time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Course {
+    width: u16,
+    height: u16,
+    rng: ChaCha12Rng,              // The generator behind `StdRng`, which can be saved mid-course
+    pub boundaries: Vec<Boundary>, // Floor and ceiling
+    pub pipes: VecDeque<(Boundary, Boundary)>, // Lower and upper pipe
+    progress: u16,
+    next_gap: u16,
+    next_height: u16,
+    next_margin: u16,
+    generated: u16,
+    extra_gap: u16, // Rows added to every gap
+    pub scroll: u32,
+}
+
+impl Course {
+    pub fn new(seed: u64, width: u16, height: u16) -> Self {
+        let boundary_string = std::iter::repeat_n('-', width as usize).collect::<String>();
+        let boundaries = vec![
+            Boundary::new(vec![boundary_string.as_str()], vec![None], 0, -1),
+            Boundary::new(vec![boundary_string.as_str()], vec![None], 0, height as i16),
+        ];
+
+        let mut course = Course {
+            width,
+            height,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            boundaries,
+            pipes: VecDeque::new(),
+            progress: 0,
+            next_gap: 0,
+            next_height: 0,
+            next_margin: 0,
+            generated: 0,
+            extra_gap: 0,
+            scroll: 0,
+        };
+        course.sample_next_pipe();
+        course.progress = 100;
+        course
+    }
+
+    /// Columns and rows it was drawn for
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Widen every gap by `rows`, as far as the pipes leave room
+    pub fn wider_gaps(self, rows: u16) -> Self {
+        Course { extra_gap: rows, ..self }
+    }
+
+    /// Whether the last pipe is gone
+    pub fn finished(&self) -> bool {
+        self.pipes.is_empty() && self.generated >= game::MAX_PIPE_NUM
+    }
+
+    /// Whether the bird hits the floor, the ceiling or a pipe
+    pub fn collides(&self, bird: &Bird) -> bool {
+        self.collision(bird).is_some()
+    }
+
+    /// What the bird hits, if anything
+    pub fn collision(&self, bird: &Bird) -> Option<DeathCause> {
+        let [floor, ceiling] =
+            [0, 1].map(|index| self.boundaries.get(index).is_some_and(|boundary| bird.collides_with(boundary)));
+        if floor {
+            Some(DeathCause::Floor)
+        } else if ceiling {
+            Some(DeathCause::Ceiling)
+        } else if self.pipes.iter().any(|(lower, upper)| bird.collides_with(lower) || bird.collides_with(upper)) {
+            Some(DeathCause::Pipe)
+        } else {
+            None
+        }
+    }
+
+    /// The pipes in view with their index on the course
+    pub fn indexed_pipes(&self) -> impl Iterator<Item = (u16, &(Boundary, Boundary))> {
+        let first = self.generated - self.pipes.len() as u16;
+        self.pipes.iter().enumerate().map(move |(index, pipes)| (first + index as u16, pipes))
+    }
+
+    /// Where the bird is relative to the first pipe it has not cleared yet
+    pub fn spot(&self, bird: &Bird) -> Option<DeathSpot> {
+        let (bird_x, bird_y) = bird.get_pos();
+        let (bird_x, bird_y) = (bird_x as i16, bird_y as i16);
+        self.indexed_pipes().find_map(|(pipe, (lower, _))| {
+            let x = lower.get_pos().0 as i16;
+            let gap = lower.get_size().1 as i16;
+            (x + lower.get_size().0 as i16 > bird_x).then_some(DeathSpot { pipe, x: bird_x - x, y: bird_y - gap })
+        })
+    }
+
+    fn rand_in(&mut self, base: u16, range: u16) -> u16 {
+        base - range + self.rng.gen::<u16>() % (2 * range)
+    }
+
+    fn generate_pipe_string(&self, width: u16, height: u16, reverse: bool) -> String {
+        let row = std::iter::repeat_n('|', width as usize).collect::<String>();
+        let edge_row = std::iter::repeat_n('█', width as usize).collect::<String>();
+
+        let rows_iter = std::iter::repeat_with(|| row.clone()).take(height as usize - 2);
+        let edge_rows_iter = std::iter::repeat_with(|| edge_row.clone()).take(2);
+        let rows: Vec<String> =
+            if reverse { edge_rows_iter.chain(rows_iter).collect() } else { rows_iter.chain(edge_rows_iter).collect() };
+
+        rows.join("\n")
+    }
+
+    fn sample_next_pipe(&mut self) {
+        self.next_gap = self.rand_in(game::PIPE_GAP_BASE, game::PIPE_GAP_RANGE);
+        self.next_height = self.rng.gen::<u16>() % (self.height - self.next_gap - 4) + 2;
+        self.next_margin = self.rand_in(game::PIPE_MARGIN_BASE, game::PIPR_MARGIN_RANGE);
+        self.progress = 0;
+    }
+
+    fn generate_pipe(&mut self) {
+        let pipe_width = game::PIPE_WIDRH; // Example width
+        let gap_height = (self.next_gap + self.extra_gap).min(self.height - 4); // Example gap height
+        let pipe_x = self.width as i16; // Start at the right edge
+
+        // Widen the gap around its middle, keeping both pipes at least 2 rows high
+        let lower_pipe_height =
+            self.next_height.saturating_sub(self.extra_gap / 2).clamp(2, self.height - gap_height - 2);
+        let upper_pipe_height = self.height - gap_height - lower_pipe_height;
+        let upper_pipe_y = lower_pipe_height + gap_height;
+
+        let lower_stirng = self.generate_pipe_string(pipe_width, lower_pipe_height, true);
+        let upper_stirng = self.generate_pipe_string(pipe_width, upper_pipe_height, false);
+
+        let colors =
+            if self.generated == game::MAX_PIPE_NUM - 1 { vec![game::LAST_PIPE_COLOR] } else { vec![game::PIPE_COLOR] };
+
+        let lower_pipe = Boundary::new(vec![lower_stirng.as_str()], colors.clone(), pipe_x, 0);
+        let upper_pipe = Boundary::new(vec![upper_stirng.as_str()], colors, pipe_x, upper_pipe_y as i16);
+
+        self.pipes.push_back((lower_pipe, upper_pipe));
+        self.generated += 1;
+    }
+
+    /// Scroll one column, and return whether a pipe passed `bird_x`
+    pub fn advance(&mut self, bird_x: i16) -> bool {
+        self.progress += 1;
+        self.scroll += 1;
+
+        // Move pipes to the left and remove those that are out of view
+        let mut passed = false;
+        for (upper, lower) in self.pipes.iter_mut() {
+            upper.move_left(1);
+            lower.move_left(1);
+            passed |= upper.get_pos().0 as i16 + upper.get_size().0 as i16 == bird_x;
+        }
+
+        let canvas = Rect::new(0, 0, self.width, self.height);
+        self.pipes.retain(|(upper, lower)| upper.visible(canvas));
+
+        if self.progress >= self.next_margin + game::PIPE_WIDRH && self.generated < game::MAX_PIPE_NUM {
+            self.generate_pipe();
+            self.sample_next_pipe();
+        }
+        passed
+    }
+}
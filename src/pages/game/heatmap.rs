@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Position, Rect},
+    style::Color,
+};
+
+use crate::{
+    constants::game,
+    pages::game::{
+        course::{Course, DeathSpot},
+        object::Object,
+    },
+};
+
+/// How often players crashed at each spot around each pipe of the course
+#[derive(Debug, Clone, Default)]
+pub struct Heatmap {
+    crashes: HashMap<DeathSpot, u32>,
+}
+
+impl Heatmap {
+    pub fn new(spots: impl IntoIterator<Item = DeathSpot>) -> Self {
+        let mut crashes = HashMap::new();
+        for spot in spots {
+            *crashes.entry(spot).or_insert(0) += 1;
+        }
+        Heatmap { crashes }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.crashes.values().sum()
+    }
+
+    /// Crashes covering each cell of `area`, counting the whole bird of `size` at each spot
+    fn cells(&self, course: &Course, size: (u16, u16), area: Rect) -> HashMap<(u16, u16), u32> {
+        let mut cells = HashMap::new();
+        for (index, (lower, _)) in course.indexed_pipes() {
+            let left = area.x as i16 + lower.get_pos().0 as i16;
+            let bottom = area.y as i16 + area.height as i16 - 1 - lower.get_size().1 as i16;
+            for (spot, count) in self.crashes.iter().filter(|(spot, _)| spot.pipe == index) {
+                for dx in 0..size.0 as i16 {
+                    for dy in 0..size.1 as i16 {
+                        let (x, y) = (left + spot.x + dx, bottom - spot.y - dy);
+                        let (Ok(x), Ok(y)) = (u16::try_from(x), u16::try_from(y)) else {
+                            continue;
+                        };
+                        if area.contains(Position::new(x, y)) {
+                            *cells.entry((x, y)).or_insert(0) += count;
+                        }
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Tint the background of the cells players crashed in, hotter where they crashed more
+    pub fn render(&self, course: &Course, size: (u16, u16), area: Rect, buf: &mut Buffer) {
+        let cells = self.cells(course, size, area);
+        let max = cells.values().copied().max().unwrap_or(1) as f32;
+        let (cold, hot) = (game::HEATMAP_COLD, game::HEATMAP_HOT);
+        for ((x, y), count) in cells {
+            let heat = count as f32 / max;
+            let mix = |cold: u8, hot: u8| (cold as f32 + (hot as f32 - cold as f32) * heat) as u8;
+            buf[(x, y)].set_bg(Color::Rgb(mix(cold.0, hot.0), mix(cold.1, hot.1), mix(cold.2, hot.2)));
+        }
+    }
+}
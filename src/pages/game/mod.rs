@@ -1,6 +1,20 @@
+mod animation;
 mod bird;
 mod boundary;
+mod clock;
+mod controller;
+pub mod course;
+mod heatmap;
+pub mod mutators;
 mod object;
+pub mod observer;
+mod pause;
+mod player;
+pub mod save;
+pub mod simulation;
+mod speedrun;
+mod tutorial;
+mod weather;
 
 use std::{
     collections::{HashMap, VecDeque},
@@ -10,7 +24,15 @@ use std::{
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
 use log::error;
-use ratatui::{prelude::*, widgets::*};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use ratatui::{
+    prelude::*,
+    symbols::Marker,
+    widgets::{
+        canvas::{Canvas, Points},
+        *,
+    },
+};
 use tokio::sync::mpsc::UnboundedSender;
 use tracing::trace;
 use tui_input::{backend::crossterm::EventHandler, Input};
@@ -20,35 +42,126 @@ use crate::{
     action::{act, Action, ActionState, Command, GameAction},
     components::{
         background::{Background, BackgroundState},
+        menu::Menu,
         multiline::MultiLine,
     },
-    config::{key_event_to_string, PageKeyBindings},
-    constants::game,
-    pages::game::{bird::Bird, boundary::Boundary, object::Object},
+    config::{
+        key_event_to_string, Assist, Config, Difficulty, LeaderboardConfig, PageKeyBindings, RenderMode, WindConfig,
+    },
+    constants::{game, HEIGHT, WIDTH},
+    daily::{self, DailyRecord},
+    leaderboard::{self, Run},
+    net::{Message, Session, Status},
+    pages::game::{
+        animation::{AnimationState, Clip},
+        bird::Bird,
+        boundary::Boundary,
+        clock::Clock,
+        controller::{Bot, Controller, Human, View},
+        course::{Course, DeathCause},
+        heatmap::Heatmap,
+        mutators::Mutators,
+        object::Object,
+        observer::{GameEvent, Observer},
+        pause::{Pause, PauseOption, PAUSE_OPTIONS},
+        player::{winner, Player, ResultOption, RESULT_OPTIONS},
+        save::SavedRun,
+        simulation::{Recorder, Replay, Simulation},
+        speedrun::{Speedrun, Splits},
+        tutorial::{tutorial_seen, CalloutTarget, Tutorial, TutorialStep},
+        weather::Weather,
+    },
+    skin::Skin,
+    stats::History,
 };
 
+#[derive(Clone, Copy)]
 enum State {
     Idle,
+    Hover,                 // Waiting for the first flap
+    Countdown(SystemTime), // Counting down to the run since
+    Connecting,            // Waiting for the opponent of a versus match
     Ready,
-    Dead,
+    Waiting,          // For the opponent to finish
+    Dead(SystemTime), // Showing the crashed bird since
+    Results(usize),   // Race results, with the index of the selected option
+    Offer(usize),     // Of skipping to the card after failed runs, with the index of the selected option
+}
+
+const OFFER_OPTIONS: [&str; 2] = ["Skip to the card", "Keep trying"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum GameMode {
+    Single,
+    Race,       // Two birds on the same course
+    Turn(u64),  // A turn of a tournament, on the course of the seed
+    Versus,     // Against another instance over the network, shown as a ghost
+    Demo,       // Flown by the bot behind the home page
+    Daily(i64), // The one attempt at the challenge of the day
+    Speedrun,   // Timed from the first flap to the last pipe
+}
+
+impl GameMode {
+    fn num_players(&self) -> usize {
+        match self {
+            GameMode::Single | GameMode::Turn(_) | GameMode::Demo | GameMode::Daily(_) | GameMode::Speedrun => 1,
+            GameMode::Race | GameMode::Versus => 2,
+        }
+    }
+
+    /// Players flying on this instance, the first ones
+    fn num_local_players(&self) -> usize {
+        match self {
+            GameMode::Versus => 1,
+            _ => self.num_players(),
+        }
+    }
+
+    /// Whether the game stops when the player asks or looks away
+    fn pausable(&self) -> bool {
+        !matches!(self, GameMode::Versus | GameMode::Demo)
+    }
 }
 
 pub struct GamePage {
     pub action_tx: Option<UnboundedSender<Action>>,
     pub keymap: PageKeyBindings,
+    render_mode: RenderMode,
+    mode: GameMode,
     state: State,
+    hover_start: SystemTime,
     canvas: Rect,
-    bird: Bird,
-    boundaries: Vec<Boundary>,
-    pipes: VecDeque<(Boundary, Boundary)>,
+    skin: Skin,
+    players: Vec<Player>,
+    controller: Box<dyn Controller>, // Of the first player
+    names: Vec<String>,              // Shown next to the scores, no scores without names
+    course: Course,
+    seed: u64, // Of the course
     pipespeed: f32,
     last_time: SystemTime,
-    progress: u16,
-    next_gap: u16,
-    next_height: u16,
-    next_margin: u16,
-    generated: u16,
-    paused: bool,
+    weather: Weather,
+    wind: WindConfig, // Of the difficulty picked, flown out of versus matches
+    pause: Pause,
+    pause_on_focus_loss: bool,
+    tutorial: Option<Tutorial>,
+    session: Option<Session>,
+    last_sent: SystemTime,      // Of the last position sent to the opponent
+    clock: Clock,               // Of the run
+    recorder: Option<Recorder>, // Of a single player run, for the leaderboard
+    leaderboard: LeaderboardConfig,
+    observers: Vec<Box<dyn Observer>>,
+    heatmap: Option<Heatmap>,   // Of past crashes, when shown
+    speedrun: Option<Speedrun>, // Of the last speedrun, kept until the next one starts
+    minimal_hud: bool,
+    mutators: Mutators,        // Of the run
+    picked_mutators: Mutators, // For single player runs
+    assist: Assist,            // Of the run
+    picked_assist: Assist,     // For single player runs
+    failed_runs: u32,          // In a row
+    saved: Option<SavedRun>,   // To continue once the canvas is known
+    steps: u32,                // Of the simulation taken in the run
+    step_time: f32,            // Seconds not stepped yet
+    scroll_time: f32,          // Seconds since the course last scrolled
 }
 
 impl GamePage {
@@ -56,51 +169,212 @@ impl GamePage {
         GamePage {
             action_tx: None,
             keymap: PageKeyBindings::default(),
+            render_mode: RenderMode::default(),
+            mode: GameMode::Single,
             state: State::Idle,
+            hover_start: SystemTime::now(),
             canvas: Rect::new(0, 0, 0, 0),
-            bird: Bird::new(Vec::from(game::BIRD_TEXTS), Vec::from(game::BIRD_COLORS), 0, 0, game::VELOCITY_LIMIT),
-            boundaries: Vec::new(),
-            pipes: VecDeque::new(),
+            skin: Skin::builtin()[0].clone(),
+            players: Vec::new(),
+            controller: Box::new(Human),
+            names: Vec::new(),
+            course: Course::new(0, WIDTH, HEIGHT),
+            seed: 0,
             pipespeed: game::PIPE_VELOCITY,
             last_time: SystemTime::now(),
-            progress: 0,
-            next_gap: 0,
-            next_height: 0,
-            next_margin: 0,
-            generated: 0,
-            paused: false,
+            weather: Weather::new(Difficulty::default().default_wind()),
+            wind: Difficulty::default().default_wind(),
+            pause: Pause::Running,
+            pause_on_focus_loss: true,
+            tutorial: None,
+            session: None,
+            last_sent: SystemTime::now(),
+            clock: Clock::new(),
+            recorder: None,
+            leaderboard: LeaderboardConfig::default(),
+            observers: Vec::new(),
+            heatmap: None,
+            speedrun: None,
+            minimal_hud: false,
+            mutators: Mutators::default(),
+            picked_mutators: Mutators::default(),
+            assist: Assist::default(),
+            picked_assist: Assist::default(),
+            failed_runs: 0,
+            saved: None,
+            steps: 0,
+            step_time: 0.0,
+            scroll_time: 0.0,
         }
     }
 
+    /// Tell `observer` about the runs of the player
+    pub fn observer(mut self, observer: Box<dyn Observer>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Observers only follow runs flown by one player at the keys
+    fn notify(&mut self, event: GameEvent) {
+        if self.controller.human() && self.mode.num_local_players() == 1 {
+            for observer in self.observers.iter_mut() {
+                observer.notify(&event);
+            }
+        }
+    }
+
+    /// A bird wearing `skin`, with the base layer recolored to `tint` if given
+    fn create_bird(skin: &Skin, tint: Option<Color>) -> Bird {
+        fn layers(layers: &[String]) -> Vec<&str> {
+            layers.iter().map(|layer| layer.as_str()).collect()
+        }
+        let base = layers(&skin.layers);
+
+        let flap_frames = if skin.flap.is_empty() {
+            vec![(base.clone(), game::BIRD_FLAP_FRAMES.iter().map(|(_, duration)| duration).sum())]
+        } else {
+            skin.flap.iter().map(|frame| (layers(&frame.layers), frame.duration)).collect()
+        };
+        let clips = HashMap::from([
+            (AnimationState::Flap, Clip::new(flap_frames, false)),
+            (AnimationState::Glide, Clip::still(base.clone())),
+            (AnimationState::Fall, Clip::still(skin.fall.as_deref().map(layers).unwrap_or(base.clone()))),
+            (AnimationState::Dead, Clip::still(skin.dead.as_deref().map(layers).unwrap_or(base.clone()))),
+        ]);
+
+        let mut colors = skin.colors.clone();
+        if let (Some(tint), Some(color)) = (tint, colors.first_mut()) {
+            *color = Some(tint);
+        }
+        Bird::new(base, colors, 0, 0, game::VELOCITY_LIMIT).animations(clips)
+    }
+
+    fn create_players(&self) -> Vec<Player> {
+        let ghost_skin = Skin { colors: vec![Some(game::GHOST_COLOR); self.skin.colors.len()], ..self.skin.clone() };
+        (0..self.mode.num_players())
+            .map(|index| {
+                match index < self.mode.num_local_players() {
+                    true => {
+                        Player::new(
+                            Self::create_bird(&self.skin, game::PLAYER_TINTS[index]).hitbox(self.mutators.hitbox()),
+                        )
+                    },
+                    false => Player::new(Self::create_bird(&ghost_skin, None)),
+                }
+            })
+            .collect()
+    }
+
+    fn local_players(&self) -> &[Player] {
+        &self.players[..self.mode.num_local_players().min(self.players.len())]
+    }
+
+    /// Hover height of the bird of player `index`, spreading the birds of a race apart
+    fn home_y(&self, index: usize) -> f32 {
+        if self.mode != GameMode::Race {
+            return self.canvas.height as f32 / 2.0;
+        }
+        let spread = ((self.players.len() as f32 - 1.0) / 2.0 - index as f32) * game::RACE_SPACING;
+        self.canvas.height as f32 / 2.0 + spread
+    }
+
+    fn start(&mut self, mode: GameMode, tutorial: bool) {
+        self.mode = mode;
+        self.names = match mode {
+            GameMode::Race => (1..=mode.num_players()).map(|index| format!("P{index}")).collect(),
+            GameMode::Versus => vec!["You".to_string(), "Opponent".to_string()],
+            GameMode::Daily(_) => vec!["Daily".to_string()],
+            _ => Vec::new(),
+        };
+        self.controller = if mode == GameMode::Demo { Box::new(Bot) } else { Box::new(Human) };
+        self.state = State::Idle;
+        self.pause = Pause::Running;
+        self.tutorial = if tutorial && mode == GameMode::Single { Some(Tutorial::new()) } else { None };
+        self.speedrun = None;
+        // Mutators and assists only change the runs of a single player, out of the tutorial
+        let single = mode == GameMode::Single && self.tutorial.is_none();
+        self.mutators = if single { self.picked_mutators } else { Mutators::default() };
+        self.assist = if single { self.picked_assist } else { Assist::default() };
+        // Versus matches fly the wind of the host once connected
+        self.weather.set_wind(self.wind);
+        self.failed_runs = 0;
+    }
+
     fn set_canvas(&mut self, canvas: Rect) {
         self.canvas = canvas;
     }
 
     fn reset(&mut self) {
-        self.state = State::Ready;
-
-        self.bird.set_pos(game::BIRD_INITIAL_X, self.canvas.height / 2);
-        self.bird.reset_time();
+        self.state = if self.mode == GameMode::Versus { State::Connecting } else { State::Hover };
+        self.hover_start = SystemTime::now();
 
-        // Floor and ceiling
-        let boundary_string = std::iter::repeat_n('-', self.canvas.width as usize).collect::<String>();
-        self.boundaries.push(Boundary::new(vec![boundary_string.as_str()], vec![None], 0, -1));
-        self.boundaries.push(Boundary::new(vec![boundary_string.as_str()], vec![None], 0, self.canvas.height as i16));
+        self.players = self.create_players();
+        for index in 0..self.players.len() {
+            let y = self.home_y(index);
+            let bird = &mut self.players[index].bird;
+            bird.set_pos(game::BIRD_INITIAL_X, y as u16);
+            bird.revive();
+        }
 
-        self.pipes = VecDeque::new();
         self.last_time = SystemTime::now();
-        self.sample_next_pipe();
-        self.progress = 100;
-        self.generated = 0;
+        let seed = match self.mode {
+            GameMode::Turn(seed) => seed,
+            GameMode::Daily(day) => daily::seed(day),
+            _ => rand::random(),
+        };
+        self.reseed(seed);
+
+        if self.heatmap.is_some() {
+            self.heatmap = Some(Heatmap::new(History::load().spots()));
+        }
+        self.recorder = None;
+    }
+
+    /// Whether the run can go on the leaderboard, which flies its replay in the simulation. The tutorial slows the
+    /// course down and assists make it easier, so their runs do not count, nor do birds of another size or winds
+    /// other than those of the difficulties.
+    fn ranked(&self) -> bool {
+        self.mode == GameMode::Single
+            && self.tutorial.is_none()
+            && !self.assist.active()
+            && Difficulty::of_wind(self.weather.wind()).is_some()
+            && self.players.first().is_some_and(|player| player.bird.get_size() == Simulation::bird_size())
+    }
+
+    /// Send the run just flown to the leaderboard, with the score the player reached. The server flies the replay
+    /// again to check it.
+    fn submit_run(&mut self) {
+        let (Some(recorder), Some(server)) = (self.recorder.take(), self.leaderboard.server.clone()) else {
+            return;
+        };
+        let score = self.players[0].score;
+        if score == 0 {
+            return;
+        }
+        let run = Run { name: self.leaderboard.name.clone(), score, replay: recorder.finish() };
+        tokio::spawn(async move {
+            match leaderboard::submit(&server, run).await {
+                Ok(rank) => log::info!("The run ranks #{rank} on the leaderboard"),
+                Err(e) => log::warn!("The leaderboard did not take the run: {e}"),
+            }
+        });
+    }
+
+    /// Start the course and the gusts drawn from `seed` over
+    fn reseed(&mut self, seed: u64) {
+        self.course = Course::new(seed, self.canvas.width, self.canvas.height).wider_gaps(self.assist.extra_gap());
+        self.seed = seed;
+        self.weather.seed(seed);
+        self.weather.reset();
     }
 
     fn draw_object<T: Object>(&self, f: &mut Frame<'_>, area: Rect, object: &T, ignore_whitespace: bool) {
         let (mut width, mut height) = object.get_size();
         let (mut x, mut y) = object.transform_pos(area);
 
-        let left = x as i16;
+        let left = x;
         let right = x + width as i16;
-        let bottom = y as i16;
+        let bottom = y;
         let top = y + height as i16;
 
         let canvas_left = self.canvas.x as i16;
@@ -108,7 +382,7 @@ impl GamePage {
         let canvas_bottom = self.canvas.y as i16;
         let canvas_top = canvas_bottom + self.canvas.height as i16;
 
-        let mut layers = object.get_layers();
+        let mut layers = object.get_frame_layers();
         for lines in layers.iter_mut() {
             if left < canvas_left || right > canvas_right {
                 let begin = (canvas_left - left).max(0) as usize;
@@ -121,7 +395,7 @@ impl GamePage {
             if bottom < canvas_bottom || top > canvas_top {
                 let begin = (canvas_bottom - bottom).max(0) as usize;
                 let end = ((height as i16) - (top - canvas_top).max(0)) as usize;
-                *lines = lines[begin..end].into_iter().map(|s| s.clone()).collect();
+                *lines = lines[begin..end].to_vec();
 
                 y += begin as i16;
                 height = (end - begin) as u16;
@@ -131,7 +405,7 @@ impl GamePage {
         let x = x as u16;
         let y = y as u16;
 
-        for (index, (lines, color)) in layers.into_iter().zip(object.get_colors().into_iter()).enumerate() {
+        for (index, (lines, color)) in layers.into_iter().zip(object.get_colors()).enumerate() {
             let lines = MultiLine::new(lines).ignore_whitespace(ignore_whitespace && index > 0);
             if let Some(color) = color {
                 let lines = lines.style(Style::default().fg(color));
@@ -142,53 +416,85 @@ impl GamePage {
         }
     }
 
-    fn rand_in(&self, base: u16, range: u16) -> u16 {
-        base - range + rand::random::<u16>() % (2 * range)
-    }
+    fn draw_pixels(&self, f: &mut Frame<'_>, area: Rect) {
+        let (marker, resolution) = match self.render_mode {
+            RenderMode::Braille => (Marker::Braille, (2, 4)),
+            _ => (Marker::HalfBlock, (1, 2)),
+        };
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
 
-    fn generate_pipe_string(&self, width: u16, height: u16, reverse: bool) -> String {
-        let row = std::iter::repeat_n('|', width as usize).collect::<String>();
-        let edge_row = std::iter::repeat_n('█', width as usize).collect::<String>();
+        let mut pixels = self
+            .players
+            .iter()
+            .rev()
+            .filter(|player| player.bird.visible(area))
+            .flat_map(|player| player.bird.get_pixels(resolution))
+            .collect::<Vec<_>>();
+        for (lower_pipe, upper_pipe) in self.shown_pipes() {
+            pixels.extend(lower_pipe.get_pixels(resolution));
+            pixels.extend(upper_pipe.get_pixels(resolution));
+        }
 
-        let rows_iter = std::iter::repeat_with(|| row.clone()).take(height as usize - 2);
-        let edge_rows_iter = std::iter::repeat_with(|| edge_row.clone()).take(2);
-        let rows: Vec<String> =
-            if reverse { edge_rows_iter.chain(rows_iter).collect() } else { rows_iter.chain(edge_rows_iter).collect() };
+        // Group pixels by color, keeping the drawing order of their first appearance
+        let mut groups: Vec<(Color, Vec<(f64, f64)>)> = Vec::new();
+        for (x, y, color) in pixels {
+            let coord = (x as f64, y as f64);
+            match groups.iter_mut().find(|(c, _)| *c == color) {
+                Some((_, coords)) => coords.push(coord),
+                None => groups.push((color, vec![coord])),
+            }
+        }
 
-        rows.join("\n")
+        let width = (area.width * resolution.0) as f64;
+        let height = (area.height * resolution.1) as f64;
+        let canvas = Canvas::default()
+            .marker(marker)
+            .background_color(Color::Black)
+            .x_bounds([0.0, width - 1.0])
+            .y_bounds([0.0, height - 1.0])
+            .paint(|ctx| {
+                for (color, coords) in &groups {
+                    ctx.draw(&Points { coords, color: *color });
+                }
+            });
+        f.render_widget(canvas, area);
     }
 
-    fn sample_next_pipe(&mut self) {
-        self.next_gap = self.rand_in(game::PIPE_GAP_BASE, game::PIPE_GAP_RANGE);
-        self.next_height = rand::random::<u16>() % (self.canvas.height - self.next_gap - 4) + 2;
-        self.next_margin = self.rand_in(game::PIPE_MARGIN_BASE, game::PIPR_MARGIN_RANGE);
-        self.progress = 0;
+    fn toggle_heatmap(&mut self) {
+        self.heatmap = match self.heatmap {
+            Some(_) => None,
+            None => Some(Heatmap::new(History::load().spots())),
+        };
     }
 
-    fn generate_pipe(&mut self) {
-        let pipe_width = game::PIPE_WIDRH; // Example width
-        let gap_height = self.next_gap; // Example gap height
-        let pipe_x = self.canvas.width as i16; // Start at the right edge
-
-        let lower_pipe_height = self.next_height;
-        let upper_pipe_height = self.canvas.height - gap_height - lower_pipe_height;
-        let upper_pipe_y = lower_pipe_height + gap_height;
-
-        let lower_stirng = self.generate_pipe_string(pipe_width, lower_pipe_height, true);
-        let upper_stirng = self.generate_pipe_string(pipe_width, upper_pipe_height, false);
-
-        let colors =
-            if self.generated == game::MAX_PIPE_NUM - 1 { vec![game::LAST_PIPE_COLOR] } else { vec![game::PIPE_COLOR] };
+    fn draw_heatmap(&self, f: &mut Frame<'_>, area: Rect) {
+        let Some(heatmap) = &self.heatmap else {
+            return;
+        };
+        let size = self.players.first().map(|player| player.bird.get_size()).unwrap_or((1, 1));
+        heatmap.render(&self.course, size, area, f.buffer_mut());
+    }
 
-        let lower_pipe = Boundary::new(vec![lower_stirng.as_str()], colors.clone(), pipe_x, 0);
-        let upper_pipe = Boundary::new(vec![upper_stirng.as_str()], colors, pipe_x, upper_pipe_y as i16);
+    /// The pipes drawn, all of them unless they are invisible
+    fn shown_pipes(&self) -> impl Iterator<Item = &(Boundary, Boundary)> {
+        let bird_x = game::BIRD_INITIAL_X as i16;
+        self.course.pipes.iter().filter(move |(lower, _)| self.mutators.pipe_visible(lower.get_pos().0 as i16 - bird_x))
+    }
 
-        self.pipes.push_back((lower_pipe, upper_pipe));
-        self.generated += 1;
+    fn draw_speedrun(&self, f: &mut Frame<'_>, area: Rect) {
+        let Some(speedrun) = &self.speedrun else {
+            return;
+        };
+        let lines = speedrun.lines(self.clock.time());
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16;
+        let area = Rect::new(area.x + 1, area.y, width, lines.len() as u16).intersection(area);
+        f.render_widget(Paragraph::new(lines), area);
     }
 
     fn draw_pipes(&self, f: &mut Frame<'_>, area: Rect) {
-        for (upper_pipe, lower_pipe) in &self.pipes {
+        for (upper_pipe, lower_pipe) in self.shown_pipes() {
             self.draw_object(f, area, upper_pipe, false);
             self.draw_object(f, area, lower_pipe, false);
         }
@@ -196,36 +502,621 @@ impl GamePage {
 
     pub fn reset_time(&mut self) {
         self.last_time = SystemTime::now();
+        self.clock.reset_time();
     }
 
-    fn get_delta_time(&mut self, now: SystemTime) -> f32 {
-        let dt = now.duration_since(self.last_time).unwrap().as_secs_f32();
-        dt
+    fn send(&self, command: Command) -> Result<()> {
+        if let Some(action_tx) = &self.action_tx {
+            action_tx.send(act!(command))?;
+        }
+        Ok(())
+    }
+
+    fn start_countdown(&mut self) {
+        self.pause = Pause::Countdown(SystemTime::now());
+    }
+
+    fn unfreeze(&mut self) {
+        self.pause = Pause::Running;
+        for player in self.players.iter_mut() {
+            player.bird.resume();
+        }
+        self.reset_time();
+
+        // The countdown to the run starts over
+        if let State::Countdown(_) = self.state {
+            self.state = State::Countdown(SystemTime::now());
+        }
+    }
+
+    fn hover(&mut self) {
+        let elapsed = self.hover_start.elapsed().unwrap_or_default().as_secs_f32();
+        let offset = game::HOVER_AMPLITUDE * (2.0 * std::f32::consts::PI * elapsed / game::HOVER_PERIOD).sin();
+        for index in 0..self.players.len() {
+            let y = self.home_y(index);
+            self.players[index].bird.hover(y + offset);
+        }
+    }
+
+    fn start_run(&mut self) {
+        self.state = State::Ready;
+        self.clock = Clock::new();
+        self.reset_time();
+        (self.steps, self.step_time, self.scroll_time) = (0, 0.0, 0.0);
+        if self.ranked() {
+            // The replay starts the bird where the simulation does, not where it hovered to
+            let (width, height) = (self.canvas.width, self.canvas.height);
+            self.players[0].bird.hover(Simulation::start_y(height));
+            let replay = Replay::new(self.seed, width, height).mutators(self.mutators).wind(self.weather.wind());
+            self.recorder = Some(Recorder::new(replay));
+        }
+        self.notify(GameEvent::RunStarted {
+            seed: self.seed,
+            mutators: self.mutators,
+            assisted: self.assist.active(),
+            tutorial: self.tutorial.is_some(),
+            time: 0.0,
+        });
+        if self.mode == GameMode::Speedrun {
+            self.speedrun = Some(Speedrun::new(Splits::load()));
+        }
+        if let GameMode::Daily(day) = self.mode {
+            self.record_daily(|record| record.attempt(day));
+        }
+    }
+
+    fn record_daily(&self, update: impl FnOnce(&mut DailyRecord)) {
+        let mut record = DailyRecord::load();
+        update(&mut record);
+        if let Err(e) = record.save() {
+            error!("Failed to save the daily challenge: {e}");
+        }
+    }
+
+    fn select_pause_option(&mut self, option: PauseOption) -> Result<()> {
+        match option {
+            PauseOption::Resume => self.start_countdown(),
+            // The attempt at the daily challenge is spent
+            PauseOption::Restart | PauseOption::Quit if matches!(self.mode, GameMode::Daily(_)) => {
+                if let GameMode::Daily(day) = self.mode {
+                    let score = self.players[0].score;
+                    self.record_daily(|record| record.score(day, score));
+                }
+                self.state = State::Idle;
+                self.pause = Pause::Running;
+                self.send(Command::ShowHome)?;
+            },
+            PauseOption::Restart => {
+                self.reset();
+                self.state = State::Countdown(SystemTime::now());
+                self.unfreeze();
+            },
+            PauseOption::Settings => self.send(Command::OpenSettings)?,
+            PauseOption::Quit => {
+                // The next run starts from scratch
+                self.state = State::Idle;
+                self.pause = Pause::Running;
+                self.send(Command::ShowHome)?;
+            },
+            PauseOption::Save => {
+                self.save_run();
+                self.state = State::Idle;
+                self.pause = Pause::Running;
+                self.send(Command::ShowHome)?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Whether the run can be saved to continue it later, only single player runs underway can
+    fn savable(&self) -> bool {
+        self.mode == GameMode::Single
+            && self.tutorial.is_none()
+            && matches!(self.state, State::Ready)
+            && self.players.first().is_some_and(|player| player.alive)
+    }
+
+    fn pause_options(&self) -> &'static [(PauseOption, &'static str)] {
+        if self.savable() {
+            &PAUSE_OPTIONS
+        } else {
+            &PAUSE_OPTIONS[..PAUSE_OPTIONS.len() - 1]
+        }
+    }
+
+    fn save_run(&self) {
+        if !self.savable() {
+            return;
+        }
+        let run = SavedRun {
+            seed: self.seed,
+            course: self.course.clone(),
+            bird: self.players[0].bird.clone(),
+            score: self.players[0].score,
+            time: self.clock.time(),
+            mutators: self.mutators,
+            assist: self.assist,
+        };
+        if let Err(e) = run.save() {
+            error!("Failed to save the run: {e}");
+        }
+    }
+
+    /// Carry on the run saved, unless it was saved on a canvas of another size
+    fn continue_run(&mut self, run: SavedRun) {
+        if !run.fits(self.canvas) {
+            log::warn!("The saved run does not fit the screen, starting a new one");
+            return;
+        }
+        self.mutators = run.mutators;
+        self.assist = run.assist;
+        self.players = self.create_players();
+        let player = &mut self.players[0];
+        player.bird.restore(&run.bird);
+        player.score = run.score;
+        self.course = run.course;
+        self.seed = run.seed;
+        self.weather.seed(run.seed);
+        self.weather.reset();
+        self.clock = Clock::at(run.time);
+        (self.steps, self.step_time, self.scroll_time) = (0, 0.0, 0.0);
+        // The replay lost the flaps before the save, so the rest of the run is not ranked
+        self.recorder = None;
+        self.notify(GameEvent::RunStarted {
+            seed: self.seed,
+            mutators: self.mutators,
+            assisted: self.assist.active(),
+            tutorial: false,
+            time: run.time,
+        });
+
+        // Give the player a countdown to pick up where they left off
+        self.state = State::Ready;
+        Page::pause(self);
+        self.start_countdown();
+    }
+
+    fn draw_pause(&self, f: &mut Frame<'_>, area: Rect) {
+        match self.pause {
+            Pause::Running => {},
+            Pause::Menu(selected) => {
+                let options = self.pause_options().iter().map(|(_, title)| title.to_string()).collect();
+                f.render_widget(Menu::new("Paused", options, selected), area);
+            },
+            Pause::Countdown(start) => self.draw_countdown(f, area, start, game::RESUME_COUNTDOWN),
+        }
+    }
+
+    /// There is no rematch over the network
+    fn result_options(&self) -> &'static [(ResultOption, &'static str)] {
+        match self.mode {
+            GameMode::Versus => &RESULT_OPTIONS[1..],
+            _ => &RESULT_OPTIONS,
+        }
+    }
+
+    fn select_result_option(&mut self, option: ResultOption) -> Result<()> {
+        match option {
+            ResultOption::Rematch => self.state = State::Idle,
+            ResultOption::Quit => {
+                if let Some(session) = self.session.take() {
+                    session.send(Message::Bye);
+                }
+                self.start(GameMode::Single, false);
+                self.send(Command::ShowHome)?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Exchange positions and results with the opponent of a versus match
+    fn sync_peer(&mut self) {
+        let Some(session) = self.session.as_ref().filter(|_| self.mode == GameMode::Versus) else {
+            return;
+        };
+        let peer = session.peer();
+
+        let player = &self.players[0];
+        let interval = self.last_sent.elapsed().unwrap_or_default().as_secs_f32();
+        if matches!(self.state, State::Ready) && player.alive && interval >= game::POSITION_INTERVAL {
+            session.send(Message::Position {
+                y: player.bird.get_pos().1,
+                score: player.score,
+                distance: self.course.scroll,
+            });
+            self.last_sent = SystemTime::now();
+        }
+
+        // Ease the ghost towards its last known position to hide the latency
+        let ghost = &mut self.players[1];
+        if let Some(y) = peer.y {
+            let (_, current) = ghost.bird.get_pos();
+            ghost.bird.hover(current + (y - current) * game::GHOST_SMOOTHING);
+        }
+        ghost.score = peer.score;
+        match (peer.over, peer.status) {
+            (Some(crashed), _) => {
+                ghost.distance = peer.distance;
+                if crashed && ghost.alive {
+                    ghost.eliminate(peer.distance);
+                }
+            },
+            (None, Status::Disconnected(_)) if ghost.alive => ghost.eliminate(peer.distance),
+            _ => {},
+        }
+    }
+
+    /// Whether the opponent of a versus match is done, by finishing or leaving
+    fn peer_done(&self) -> bool {
+        self.session.as_ref().is_none_or(|session| {
+            let peer = session.peer();
+            peer.over.is_some() || matches!(peer.status, Status::Disconnected(_))
+        })
+    }
+
+    fn draw_connecting(&self, f: &mut Frame<'_>, area: Rect) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        let text = match (session.peer().status, session.is_host()) {
+            (Status::Connecting, true) => format!("Waiting for an opponent on {}", session.address()),
+            (Status::Connecting, false) => format!("Connecting to {}", session.address()),
+            (Status::Connected, _) => "Connected, starting...".to_string(),
+            (Status::Disconnected(reason), _) => reason,
+        };
+        f.render_widget(Menu::new("Versus", vec!["Quit to Home".to_string()], 0).text(vec![text]), area);
+    }
+
+    /// Move the birds of the local players, and return what the first one crashed into, if it just did
+    fn update_players(&mut self, dt: f32) -> Option<DeathCause> {
+        let mut crash = None;
+        let gravity = self.mutators.gravity(game::GRAVITY - self.weather.lift());
+        let num_local_players = self.mode.num_local_players();
+        for (index, player) in self.players.iter_mut().take(num_local_players).enumerate() {
+            // Knocked out birds fall off the screen
+            if player.alive || player.bird.visible(self.canvas) {
+                player.bird.step(gravity, dt);
+            }
+            if !player.alive {
+                continue;
+            }
+            if let Some(cause) = self.course.collision(&player.bird) {
+                // Invincible birds fly through pipes, and stay between the floor and the ceiling
+                if self.assist.invincible {
+                    let top = self.canvas.height.saturating_sub(player.bird.get_size().1) as f32;
+                    match cause {
+                        DeathCause::Floor => player.bird.hover(0.0),
+                        DeathCause::Ceiling => player.bird.hover(top),
+                        DeathCause::Pipe => {},
+                    }
+                    continue;
+                }
+                player.eliminate(self.course.scroll);
+                if index == 0 {
+                    crash = Some(cause);
+                }
+            }
+        }
+        crash
+    }
+
+    fn finish_race(&mut self) {
+        let num_local_players = self.mode.num_local_players();
+        for player in self.players.iter_mut().take(num_local_players).filter(|player| player.alive) {
+            player.distance = self.course.scroll;
+        }
+        self.state = State::Results(0);
+    }
+
+    fn draw_scores(&self, f: &mut Frame<'_>, area: Rect) {
+        let spans = self
+            .players
+            .iter()
+            .enumerate()
+            .take(self.names.len())
+            .flat_map(|(index, player)| {
+                let color = player.bird.get_colors().first().copied().flatten().unwrap_or(Color::White);
+                let style = if player.alive { Style::default().fg(color).bold() } else { Style::default().fg(color) };
+                [Span::styled(format!(" {} {} ", self.names[index], player.score), style), Span::raw(" ")]
+            })
+            .collect::<Vec<_>>();
+        let area = Rect::new(area.x, area.y, area.width, 1.min(area.height));
+        f.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn draw_results(&self, f: &mut Frame<'_>, area: Rect, selected: usize) {
+        let headline = match (winner(&self.players), self.mode) {
+            (Some(0), GameMode::Versus) => "You win!".to_string(),
+            (Some(_), GameMode::Versus) => "The opponent wins!".to_string(),
+            (Some(index), _) => format!("Player {} wins!", index + 1),
+            (None, _) => "It's a tie!".to_string(),
+        };
+        let mut text = vec![headline, String::new()];
+        text.extend(self.players.iter().enumerate().map(|(index, player)| {
+            let name = match self.mode {
+                GameMode::Versus => self.names[index].clone(),
+                _ => format!("Player {}", index + 1),
+            };
+            let status = if player.alive { "finished" } else { "crashed" };
+            format!("{name}: {} pipes, {status}", player.score)
+        }));
+        let left = self.session.as_ref().is_some_and(|session| {
+            let peer = session.peer();
+            peer.over.is_none() && matches!(peer.status, Status::Disconnected(_))
+        });
+        if left {
+            text.push("The opponent disconnected".to_string());
+        }
+        let options = self.result_options().iter().map(|(_, title)| title.to_string()).collect();
+        let title = if self.mode == GameMode::Versus { "Match over" } else { "Race over" };
+        f.render_widget(Menu::new(title, options, selected).text(text), area);
     }
 
-    fn update_pipes(&mut self) {
-        let now = SystemTime::now();
-        let dt = self.get_delta_time(now);
+    /// Skipping to the card is offered with the assist, after failing enough runs in a row
+    fn select_offer_option(&mut self, selected: usize) -> Result<()> {
+        if selected == 0 {
+            self.state = State::Idle;
+            self.send(Command::ShowCard)?;
+        } else {
+            self.failed_runs = 0;
+            self.reset();
+        }
+        Ok(())
+    }
 
-        if dt >= (1.0 / self.pipespeed) {
-            self.last_time = now;
-            self.progress += 1;
+    fn draw_offer(&self, f: &mut Frame<'_>, area: Rect, selected: usize) {
+        let text = vec![format!("That was {} tries in a row", self.failed_runs)];
+        let options = OFFER_OPTIONS.iter().map(|title| title.to_string()).collect();
+        f.render_widget(Menu::new("Need a hand?", options, selected).text(text), area);
+    }
 
-            // Move pipes to the left and remove those that are out of view
-            for (upper, lower) in self.pipes.iter_mut() {
-                upper.move_left(1);
-                lower.move_left(1);
+    fn draw_countdown(&self, f: &mut Frame<'_>, area: Rect, start: SystemTime, seconds: f32) {
+        let elapsed = start.elapsed().unwrap_or_default().as_secs_f32();
+        let remaining = (seconds - elapsed).ceil().max(1.0) as u32;
+        let [area] = Layout::horizontal([Constraint::Length(7)]).flex(layout::Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(3)]).flex(layout::Flex::Center).areas(area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Cyan))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(remaining.to_string()).alignment(Alignment::Center).bold().block(block), area);
+    }
+
+    fn flap_key(&self, action: GameAction) -> String {
+        let command = Command::Game(action);
+        self.keymap
+            .0
+            .iter()
+            .find(|(_, action)| action.command == command && action.state == ActionState::Start)
+            .map(|(key, _)| key_event_to_string(key))
+            .unwrap_or("Space".to_string())
+    }
+
+    /// Screen area of `object`, clipped to the canvas
+    fn screen_rect<T: Object>(&self, area: Rect, object: &T) -> Rect {
+        let (x, y) = object.transform_pos(area);
+        let (width, height) = object.get_size();
+        let left = x.max(area.x as i16);
+        let top = y.max(area.y as i16);
+        let right = (x + width as i16).min(area.right() as i16);
+        let bottom = (y + height as i16).min(area.bottom() as i16);
+        Rect::new(left as u16, top as u16, (right - left).max(0) as u16, (bottom - top).max(0) as u16)
+    }
+
+    fn draw_tutorial(&self, f: &mut Frame<'_>, area: Rect) {
+        let Some((text, target)) =
+            self.tutorial.as_ref().and_then(|tutorial| tutorial.callout(&self.flap_key(GameAction::Up)))
+        else {
+            return;
+        };
+
+        let target = match target {
+            CalloutTarget::Bird => self.players.first().map(|player| self.screen_rect(area, &player.bird)),
+            CalloutTarget::NextPipe => {
+                // Point at the gap of the first pipe ahead of the bird
+                let bird_x = game::BIRD_INITIAL_X as f32;
+                self.course
+                    .pipes
+                    .iter()
+                    .find(|(lower, _)| lower.get_pos().0 + lower.get_size().0 as f32 > bird_x && lower.visible(area))
+                    .map(|(lower, upper)| {
+                        let lower = self.screen_rect(area, lower);
+                        let upper = self.screen_rect(area, upper);
+                        Rect::new(lower.x, upper.bottom(), lower.width, lower.y.saturating_sub(upper.bottom()))
+                    })
+            },
+            CalloutTarget::Nothing => None,
+        };
+        let color = match self.tutorial.as_ref().map(|tutorial| tutorial.step()) {
+            Some(TutorialStep::LastPipe) => game::LAST_PIPE_COLOR.unwrap_or(game::CALLOUT_COLOR),
+            _ => game::CALLOUT_COLOR,
+        };
+        self.draw_callout(f, area, &text, target, color);
+    }
+
+    /// A box of text with an arrow to `target`, or at the top of the canvas without a target
+    fn draw_callout(&self, f: &mut Frame<'_>, area: Rect, text: &str, target: Option<Rect>, color: Color) {
+        let lines = text.lines().map(Line::from).collect::<Vec<_>>();
+        let width = (lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 4).min(area.width);
+        let height = (lines.len() as u16 + 2).min(area.height);
+
+        let (x, y) = match target {
+            Some(target) => {
+                let center = target.x + target.width / 2;
+                let x = center.saturating_sub(width / 2).clamp(area.x, area.right().saturating_sub(width));
+                let (y, arrow_y, arrow) = if target.y > area.y + height {
+                    (target.y - height - 1, target.y - 1, "▼")
+                } else {
+                    (target.bottom() + 1, target.bottom(), "▲")
+                };
+                let arrow_area = Rect::new(center, arrow_y, 1, 1).intersection(area);
+                f.render_widget(Paragraph::new(arrow).style(Style::default().fg(color)), arrow_area);
+                (x, y.min(area.bottom().saturating_sub(height)))
+            },
+            None => (area.x + area.width.saturating_sub(width) / 2, area.y + 1),
+        };
+
+        let callout_area = Rect::new(x, y, width, height).intersection(area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(color))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(Clear, callout_area);
+        f.render_widget(Paragraph::new(lines).alignment(Alignment::Center).block(block), callout_area);
+    }
+
+    fn draw_prompt(&self, f: &mut Frame<'_>, area: Rect) {
+        let prompt = match self.mode {
+            GameMode::Single | GameMode::Versus | GameMode::Demo => {
+                format!("Press {} to flap", self.flap_key(GameAction::Up))
+            },
+            GameMode::Turn(_) => format!("{}: press {} to flap", self.names[0], self.flap_key(GameAction::Up)),
+            GameMode::Daily(_) => format!("Daily challenge: one try, press {} to flap", self.flap_key(GameAction::Up)),
+            GameMode::Speedrun => format!("Press {} to flap and start the timer", self.flap_key(GameAction::Up)),
+            GameMode::Race => {
+                format!(
+                    "Player 1: {} / Player 2: {} to flap",
+                    self.flap_key(GameAction::Up),
+                    self.flap_key(GameAction::PlayerTwoUp)
+                )
+            },
+        };
+        let [area] = Layout::vertical([Constraint::Length(1)]).flex(layout::Flex::Center).areas(area);
+        let area = area.offset(layout::Offset { x: 0, y: game::PROMPT_OFFSET }).intersection(f.area());
+        f.render_widget(Paragraph::new(prompt).alignment(Alignment::Center).bold(), area);
+    }
+
+    fn get_delta_time(&mut self, now: SystemTime) -> f32 {
+        now.duration_since(self.last_time).unwrap().as_secs_f32()
+    }
+
+    /// Flap the bird of player `index` before the next step
+    fn flap(&mut self, index: usize) {
+        let velocity = self.mutators.up_velocity();
+        self.players[index].bird.up(velocity);
+        self.clock.update();
+        let time = self.clock.time();
+        if index == 0 {
+            if let Some(recorder) = self.recorder.as_mut() {
+                recorder.flap(self.steps);
             }
+            self.notify(GameEvent::Flap { time });
+        }
+        if let Some(tutorial) = self.tutorial.as_mut() {
+            tutorial.on_flap();
+        }
+    }
 
-            // self.pipes.retain(|(upper, lower)| upper.visible(self.canvas));
-            self.pipes.retain(|(upper, lower)| upper.visible(self.canvas));
+    /// Take the fixed steps due in `dt` seconds, as many as the simulation would in that time, while the run lasts
+    fn advance(&mut self, dt: f32) -> Result<()> {
+        self.step_time += dt;
+        while self.step_time >= game::SIM_STEP && matches!(self.state, State::Ready) {
+            self.step_time -= game::SIM_STEP;
+            self.step()?;
+        }
+        Ok(())
+    }
+
+    /// Move the wind, the course and the birds by one step of the simulation, so that replays fly runs the same way
+    fn step(&mut self) -> Result<()> {
+        self.steps += 1;
+        self.weather.step(game::SIM_STEP);
+        let view = View { bird: &self.players[0].bird, pipes: &self.course.pipes, height: self.canvas.height };
+        if self.players[0].alive && self.controller.update(&view) {
+            self.players[0].bird.up(self.mutators.up_velocity());
+        }
+        let passed = self.update_pipes(game::SIM_STEP);
+        let crash = self.update_players(game::SIM_STEP);
+        if passed {
+            let num_local_players = self.mode.num_local_players();
+            for player in self.players.iter_mut().take(num_local_players).filter(|player| player.alive) {
+                player.score += 1;
+            }
+        }
+        let (score, alive, time) = (self.players[0].score, self.players[0].alive, self.clock.time());
+        if passed && alive {
+            self.notify(GameEvent::PipePassed { score, time });
+            let record = self.speedrun.as_mut().is_some_and(|speedrun| speedrun.pipe_passed(score, time));
+            if let (true, Some(speedrun)) = (record, &self.speedrun) {
+                if let Err(e) = speedrun.splits().save() {
+                    error!("Failed to save the splits: {e}");
+                }
+            }
+        }
+        if let Some(cause) = crash {
+            let spot = self.course.spot(&self.players[0].bird);
+            self.notify(GameEvent::Collision { score, time, cause, spot });
+        }
 
-            // if self.progress >= self.next_margin + game::PIPE_WIDRH && self.generated < game::MAX_PIPE_NUM {
-            if self.progress >= self.next_margin + game::PIPE_WIDRH && self.generated < game::MAX_PIPE_NUM {
-                self.generate_pipe();
-                self.sample_next_pipe();
+        if let Some(tutorial) = self.tutorial.as_mut() {
+            if passed {
+                tutorial.on_pipe_passed();
             }
+            tutorial.update();
+            if tutorial.finished() {
+                self.tutorial = None;
+            }
+        }
+
+        let finished = self.course.finished();
+        let game_over = self.local_players().iter().all(|player| !player.alive);
+        if game_over || finished {
+            self.notify(GameEvent::RunFinished { score, crashed: !alive, time });
+        }
+        match self.mode {
+            GameMode::Single if game_over => {
+                self.submit_run();
+                self.failed_runs += 1;
+                self.state = State::Dead(SystemTime::now());
+            },
+            GameMode::Demo | GameMode::Speedrun if game_over || finished => self.reset(),
+            GameMode::Single if finished => {
+                self.submit_run();
+                self.state = State::Idle;
+                self.send(Command::ShowCard)?;
+            },
+            GameMode::Race if game_over || finished => self.finish_race(),
+            GameMode::Versus if game_over || finished => {
+                let player = &self.players[0];
+                let distance = if player.alive { self.course.scroll } else { player.distance };
+                if let Some(session) = &self.session {
+                    session.send(Message::Over { score: player.score, distance, crashed: !player.alive });
+                }
+                self.state = State::Waiting;
+            },
+            GameMode::Daily(day) if game_over || finished => {
+                self.record_daily(|record| record.score(day, score));
+                self.state = State::Idle;
+                self.send(Command::ShowHome)?;
+            },
+            GameMode::Turn(_) if game_over || finished => {
+                self.state = State::Idle;
+                self.send(Command::TurnOver(self.players[0].score))?;
+            },
+            _ => {},
         }
+        Ok(())
+    }
+
+    /// Scroll the course by the columns due in `dt` seconds, and return whether a pipe passed the bird
+    fn update_pipes(&mut self, dt: f32) -> bool {
+        // A headwind slows the course down, and so does the tutorial
+        let mut pipespeed = self.mutators.pipe_speed(self.pipespeed) * (1.0 - self.weather.drag());
+        if self.tutorial.is_some() {
+            pipespeed *= game::TUTORIAL_PIPE_SPEED;
+        }
+        // The time left over counts towards the next column, as in the simulation
+        self.scroll_time += dt;
+        let scroll_interval = 1.0 / pipespeed;
+        let mut passed = false;
+        while self.scroll_time >= scroll_interval {
+            self.scroll_time -= scroll_interval;
+            passed |= self.course.advance(game::BIRD_INITIAL_X as i16);
+        }
+        passed
     }
 }
 
@@ -242,54 +1133,200 @@ impl Page for GamePage {
     }
 
     fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        for observer in self.observers.iter_mut() {
+            observer.register_action_handler(tx.clone());
+        }
         self.action_tx = Some(tx);
         Ok(())
     }
 
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.render_mode = config.settings.render_mode;
+        self.wind = config.wind();
+        if self.mode != GameMode::Versus {
+            self.weather.set_wind(self.wind);
+        }
+        self.pause_on_focus_loss = config.settings.pause_on_focus_loss;
+        self.minimal_hud = config.settings.minimal_hud;
+        self.picked_mutators = config.settings.mutators;
+        self.picked_assist = config.settings.assist;
+        self.leaderboard = config.leaderboard.clone();
+
+        self.skin = Skin::find(&Skin::load_all(), &config.settings.skin);
+        let birds = self.create_players().into_iter().map(|player| player.bird);
+        for (player, bird) in self.players.iter_mut().zip(birds) {
+            player.bird.wear(bird);
+        }
+        Ok(())
+    }
+
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         Ok(None)
     }
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action.command {
+            Command::StartGame => self.start(GameMode::Single, !tutorial_seen()),
+            Command::StartTutorial => self.start(GameMode::Single, true),
+            Command::StartRace => self.start(GameMode::Race, false),
+            Command::StartVersus => self.start(GameMode::Versus, false),
+            Command::StartDemo => self.start(GameMode::Demo, false),
+            Command::StartSpeedrun => self.start(GameMode::Speedrun, false),
+            Command::ContinueRun => {
+                self.start(GameMode::Single, false);
+                self.saved = SavedRun::load();
+                // A saved run can only be continued once
+                if let Err(e) = SavedRun::remove() {
+                    error!("Failed to remove the saved run: {e}");
+                }
+            },
+            // Quitting the app in the middle of a run suspends it
+            Command::Quit => self.save_run(),
+            Command::StopDemo => self.start(GameMode::Single, false),
+            Command::StartDaily(day) => self.start(GameMode::Daily(day), false),
+            Command::StartTurn(ref name, seed) => {
+                self.start(GameMode::Turn(seed), false);
+                self.names = vec![name.clone()];
+            },
+            _ => {},
+        }
+
+        // Wait for an explicit resume when the focus comes back
+        let can_pause = self.mode.pausable() && !matches!(self.pause, Pause::Menu(_));
+        if action.command == Command::FocusLost && self.pause_on_focus_loss && can_pause {
+            self.pause();
+        }
+
         if let Command::Game(command) = action.command {
-            match command {
-                GameAction::Up if action.state == ActionState::End => {
-                    self.bird.up(game::UP_VELOCITY);
+            let player = match command {
+                GameAction::Up => Some(0),
+                GameAction::PlayerTwoUp if self.mode == GameMode::Race => Some(1),
+                _ => None,
+            };
+            match (self.pause, command) {
+                (Pause::Running, GameAction::Up | GameAction::PlayerTwoUp) if action.state == ActionState::End => {
+                    match (self.state, player) {
+                        // The timer starts with the first flap, without a countdown
+                        (State::Hover, Some(_)) if self.mode == GameMode::Speedrun => {
+                            self.start_run();
+                            self.players[0].bird.up(self.mutators.up_velocity());
+                            self.notify(GameEvent::Flap { time: 0.0 });
+                        },
+                        (State::Hover, Some(_)) if self.controller.human() => {
+                            self.state = State::Countdown(SystemTime::now())
+                        },
+                        (State::Ready, Some(index)) if self.players[index].alive && self.controller.human() => {
+                            self.flap(index)
+                        },
+                        _ => {},
+                    }
+                },
+                (Pause::Running, GameAction::MenuUp) => {
+                    match self.state {
+                        State::Results(selected) => self.state = State::Results(selected.saturating_sub(1)),
+                        State::Offer(selected) => self.state = State::Offer(selected.saturating_sub(1)),
+                        _ => {},
+                    }
+                },
+                (Pause::Running, GameAction::MenuDown) => {
+                    match self.state {
+                        State::Results(selected) => {
+                            self.state = State::Results((selected + 1).min(self.result_options().len() - 1))
+                        },
+                        State::Offer(selected) => {
+                            self.state = State::Offer((selected + 1).min(OFFER_OPTIONS.len() - 1))
+                        },
+                        _ => {},
+                    }
+                },
+                (Pause::Running, GameAction::Select) => {
+                    match self.state {
+                        State::Results(selected) => self.select_result_option(self.result_options()[selected].0)?,
+                        State::Offer(selected) => self.select_offer_option(selected)?,
+                        State::Connecting => self.select_result_option(ResultOption::Quit)?,
+                        _ => {},
+                    }
                 },
+                (_, GameAction::Pause)
+                    if matches!(self.state, State::Results(_) | State::Offer(_)) || !self.mode.pausable() => {},
+                (Pause::Running | Pause::Countdown(_), GameAction::Pause) => self.pause(),
+                (Pause::Menu(_), GameAction::Pause) => self.start_countdown(),
+                (Pause::Menu(selected), GameAction::MenuUp) => self.pause = Pause::Menu(selected.saturating_sub(1)),
+                (Pause::Menu(selected), GameAction::MenuDown) => {
+                    self.pause = Pause::Menu((selected + 1).min(self.pause_options().len() - 1))
+                },
+                (Pause::Menu(selected), GameAction::Select) => {
+                    self.select_pause_option(self.pause_options()[selected].0)?
+                },
+                (_, GameAction::ToggleHeatmap) => self.toggle_heatmap(),
                 _ => {},
             }
         }
 
+        if let Pause::Countdown(start) = self.pause {
+            if start.elapsed().unwrap_or_default().as_secs_f32() >= game::RESUME_COUNTDOWN {
+                self.unfreeze();
+            }
+        }
+
         match self.state {
             State::Idle => {},
-            State::Ready => {
-                if !self.paused {
-                    self.bird.update(game::GRAVITY);
-                    self.update_pipes();
-
-                    if self.pipes.len() == 0 && self.generated >= game::MAX_PIPE_NUM {
-                        if let Some(action_tx) = &self.action_tx {
-                            action_tx.send(act!(Command::ShowCard))?;
-                        }
-                    }
-
-                    let mut game_over = false;
-                    if self.bird.collides_with(&self.boundaries[0]) || self.bird.collides_with(&self.boundaries[1]) {
-                        game_over = true;
-                    }
-                    for (lower_pipe, upper_pipe) in self.pipes.iter() {
-                        if self.bird.collides_with(lower_pipe) || self.bird.collides_with(upper_pipe) {
-                            game_over = true;
-                            break;
-                        }
+            State::Hover => {
+                if self.pause.running() {
+                    self.hover();
+                }
+                // The bot does not wait
+                if !self.controller.human() {
+                    self.start_run();
+                }
+            },
+            State::Connecting => {
+                // Both sides start counting down once the host picked the course and the wind
+                let peer = self.session.as_ref().map(|session| session.peer());
+                if let Some((seed, wind)) = peer.and_then(|peer| peer.seed.zip(peer.wind)) {
+                    self.weather.set_wind(wind);
+                    self.reseed(seed);
+                    self.state = State::Countdown(SystemTime::now());
+                }
+            },
+            State::Countdown(start) => {
+                if self.pause.running() {
+                    self.hover();
+                    self.sync_peer();
+                    if start.elapsed().unwrap_or_default().as_secs_f32() >= game::START_COUNTDOWN {
+                        self.start_run();
                     }
-
-                    if game_over {
+                }
+            },
+            State::Ready => {
+                if self.pause.running() {
+                    self.clock.update();
+                    let now = SystemTime::now();
+                    let dt = self.get_delta_time(now) * self.assist.time_scale();
+                    self.last_time = now;
+                    self.advance(dt)?;
+                    self.sync_peer();
+                }
+            },
+            State::Waiting => {
+                self.sync_peer();
+                if self.peer_done() {
+                    self.finish_race();
+                }
+            },
+            // The crashed bird stays on screen for a moment before the next run
+            State::Dead(since) => {
+                if self.pause.running() && since.elapsed().unwrap_or_default().as_secs_f32() >= game::DEAD_DELAY {
+                    let skip_after = self.assist.skip_after;
+                    if skip_after > 0 && self.failed_runs >= skip_after {
+                        self.state = State::Offer(0);
+                    } else {
                         self.reset();
                     }
                 }
             },
-            State::Dead => {},
+            State::Offer(_) => {},
+            State::Results(_) => {},
         }
 
         Ok(None)
@@ -299,45 +1336,178 @@ impl Page for GamePage {
         if let State::Idle = self.state {
             self.set_canvas(area);
             self.reset();
+            if let Some(run) = self.saved.take() {
+                self.continue_run(run);
+            }
         }
 
-        // Draw player
-        self.draw_object(f, area, &self.bird, true);
-        // Draw pipes
-        self.draw_pipes(f, area);
+        if self.render_mode == RenderMode::Text {
+            // Draw players
+            for player in self.players.iter().rev().filter(|player| player.bird.visible(area)) {
+                self.draw_object(f, area, &player.bird, true);
+            }
+            // Draw pipes
+            self.draw_pipes(f, area);
+        } else {
+            self.draw_pixels(f, area);
+        }
+        let hud = !(self.minimal_hud && self.mode == GameMode::Speedrun);
+        if hud {
+            self.draw_heatmap(f, area);
+        }
+        self.mutators.mirror(area, f.buffer_mut());
+        if let (Some(heatmap), true) = (&self.heatmap, hud) {
+            let legend = Line::from(format!("Heatmap of {} crashes", heatmap.total())).dark_gray();
+            f.render_widget(legend.right_aligned(), area);
+        }
 
+        match self.state {
+            State::Hover if self.pause.running() && self.tutorial.is_none() && self.controller.human() && hud => {
+                self.draw_prompt(f, area)
+            },
+            State::Countdown(start) if self.pause.running() => {
+                self.draw_countdown(f, area, start, game::START_COUNTDOWN)
+            },
+            State::Connecting => self.draw_connecting(f, area),
+            State::Waiting => {
+                let text = vec!["Waiting for the opponent to finish".to_string()];
+                f.render_widget(Menu::new("Versus", Vec::new(), 0).text(text), area);
+            },
+            State::Results(selected) => self.draw_results(f, area, selected),
+            State::Offer(selected) => self.draw_offer(f, area, selected),
+            _ => {},
+        }
+        if !self.names.is_empty() && hud {
+            self.draw_scores(f, area);
+        }
+        self.draw_speedrun(f, area);
+        if self.pause.running() && hud {
+            self.draw_tutorial(f, area);
+        }
+        self.draw_pause(f, area);
+
+        Ok(())
+    }
+
+    fn register_session_handler(&mut self, session: Session) -> Result<()> {
+        self.session = Some(session);
         Ok(())
     }
 
+    fn update_background(&mut self, state: &mut BackgroundState) {
+        state.set_scroll(self.course.scroll as f32);
+        state.set_wind(self.weather.direction());
+    }
+
     fn pause(&mut self) {
-        self.paused = true;
+        // Versus and the demo have no pause menu and keep running
+        if !self.mode.pausable() {
+            return;
+        }
+        for player in self.players.iter_mut() {
+            player.bird.pause();
+        }
+        self.pause = Pause::Menu(0);
     }
 
+    // The pause menu stays open behind the help, and the time the help was open does not count
     fn resume(&mut self) {
-        self.bird.resume();
-        self.paused = false;
+        self.reset_time();
+    }
+
+    fn runs_behind_help(&self) -> bool {
+        !self.mode.pausable()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{cell::Cell, rc::Rc};
+
     use super::*;
+    use crate::achievements::{AchievementTracker, Unlocked};
 
-    // #[test]
-    // fn test_player() -> Result<()> {
-    //     let bird = Bird::new(game::BIRD_TEXT, 0, 0, 10.0);
-    //     println!("{:?}", bird);
+    /// Keeps the score of the last run finished
+    struct Finish(Rc<Cell<Option<u16>>>);
 
-    //     Ok(())
-    // }
+    impl Observer for Finish {
+        fn notify(&mut self, event: &GameEvent) {
+            if let GameEvent::RunFinished { score, .. } = *event {
+                self.0.set(Some(score));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ranked_runs_replay() -> Result<()> {
+        let finished = Rc::new(Cell::new(None));
+        let mut game = GamePage::new().observer(Box::new(Finish(finished.clone())));
+        game.wind = game::WIND_HARD;
+        game.set_canvas(Rect::new(0, 0, WIDTH, HEIGHT));
+        game.start(GameMode::Single, false);
+        game.reset();
+        game.start_run();
+
+        // Frames of uneven length, flapping between them like the bot would
+        let mut recorder = None;
+        for frame in 0..100_000 {
+            recorder = game.recorder.clone();
+            let view = View { bird: &game.players[0].bird, pipes: &game.course.pipes, height: game.canvas.height };
+            if Bot.update(&view) {
+                game.flap(0);
+            }
+            game.advance([0.011, 0.017, 0.033, 0.052][frame % 4])?;
+            if finished.get().is_some() {
+                break;
+            }
+        }
+
+        let replay = recorder.expect("The run is ranked").finish();
+        let (score, _) = replay.play()?;
+        assert!(score > 0 && !replay.flaps.is_empty());
+        assert_eq!(Some(score), finished.get());
+        Ok(())
+    }
 
-    // #[test]
-    // fn test_collides() {
-    //     let bird = Bird::new(game::BIRD_TEXT, 0, 10, 10.0);
+    #[test]
+    fn test_resumed_runs_unlock_nothing() -> Result<()> {
+        let tracker = AchievementTracker::unsaved(Unlocked::default(), || (2025, 6, 1));
+        let mut game = GamePage::new().observer(Box::new(tracker));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        game.register_action_handler(tx)?;
+        game.set_canvas(Rect::new(0, 0, WIDTH, HEIGHT));
+        game.start(GameMode::Single, false);
+        game.reset();
 
-    //     let boundary_string = std::iter::repeat_n('-', 80).collect::<String>();
-    //     let bound = Boundary::new(boundary_string.as_str(), 0, 9);
+        let assisted = Assist { invincible: true, ..Assist::default() };
+        for assist in [assisted, Assist::default()] {
+            let run = SavedRun {
+                seed: game.seed,
+                course: Course::new(game.seed, WIDTH, HEIGHT),
+                bird: game.players[0].bird.clone(),
+                score: 0,
+                time: 20.0,
+                mutators: Mutators::default(),
+                assist,
+            };
+            let run = serde_json::from_str(&serde_json::to_string(&run)?)?;
+            game.continue_run(run);
 
-    //     println!("{}", bird.collides_with(&bound));
-    // }
+            // The assisted run passes pipes without a crash, the other flaps right after the glide before the save
+            game.flap(0);
+            while assist.active() && matches!(game.state, State::Ready) {
+                let view = View { bird: &game.players[0].bird, pipes: &game.course.pipes, height: game.canvas.height };
+                if Bot.update(&view) {
+                    game.flap(0);
+                }
+                game.advance(0.05)?;
+            }
+        }
+
+        let unlocks = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter(|action| matches!(action.command, Command::AchievementUnlocked(_)))
+            .count();
+        assert_eq!(unlocks, 0);
+        Ok(())
+    }
 }
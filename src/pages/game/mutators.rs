@@ -0,0 +1,184 @@
+use ratatui::{buffer::Buffer, layout::Rect};
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{game, mutators};
+
+/// Twists on a run, picked before it starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mutator {
+    ReverseGravity,
+    Mirror,
+    TinyBird,
+    FastPipes,
+    InvisiblePipes,
+}
+
+pub const MUTATORS: [Mutator; 5] =
+    [Mutator::ReverseGravity, Mutator::Mirror, Mutator::TinyBird, Mutator::FastPipes, Mutator::InvisiblePipes];
+
+impl Mutator {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Mutator::ReverseGravity => "Reverse gravity",
+            Mutator::Mirror => "Mirror mode",
+            Mutator::TinyBird => "Tiny bird",
+            Mutator::FastPipes => "Fast pipes",
+            Mutator::InvisiblePipes => "Invisible pipes",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Mutator::ReverseGravity => "The bird falls up and flaps down",
+            Mutator::Mirror => "The course comes from the left",
+            Mutator::TinyBird => "Only the middle of the bird can be hit",
+            Mutator::FastPipes => "The pipes come half again as fast",
+            Mutator::InvisiblePipes => "Pipes only flash as they get close",
+        }
+    }
+
+    /// Shown in the scores
+    pub fn label(&self) -> &'static str {
+        match self {
+            Mutator::ReverseGravity => "rev",
+            Mutator::Mirror => "mir",
+            Mutator::TinyBird => "tiny",
+            Mutator::FastPipes => "fast",
+            Mutator::InvisiblePipes => "inv",
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The mutators on for a run, which stack. Saved as a list of names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "Vec<Mutator>", into = "Vec<Mutator>")]
+pub struct Mutators(u8);
+
+impl From<Vec<Mutator>> for Mutators {
+    fn from(list: Vec<Mutator>) -> Self {
+        Mutators(list.into_iter().fold(0, |bits, mutator| bits | mutator.bit()))
+    }
+}
+
+impl From<Mutators> for Vec<Mutator> {
+    fn from(mutators: Mutators) -> Self {
+        mutators.iter().collect()
+    }
+}
+
+impl Mutators {
+    pub fn contains(&self, mutator: Mutator) -> bool {
+        self.0 & mutator.bit() != 0
+    }
+
+    pub fn toggle(&mut self, mutator: Mutator) {
+        self.0 ^= mutator.bit();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Mutator> + '_ {
+        MUTATORS.into_iter().filter(|mutator| self.contains(*mutator))
+    }
+
+    pub fn labels(&self) -> String {
+        self.iter().map(|mutator| mutator.label()).collect::<Vec<_>>().join(" ")
+    }
+
+    pub fn gravity(&self, gravity: f32) -> f32 {
+        if self.contains(Mutator::ReverseGravity) {
+            -gravity
+        } else {
+            gravity
+        }
+    }
+
+    pub fn up_velocity(&self) -> f32 {
+        self.gravity(game::UP_VELOCITY)
+    }
+
+    pub fn pipe_speed(&self, speed: f32) -> f32 {
+        if self.contains(Mutator::FastPipes) {
+            speed * mutators::FAST_PIPES
+        } else {
+            speed
+        }
+    }
+
+    /// Fraction of the size of the bird it can be hit on
+    pub fn hitbox(&self) -> f32 {
+        if self.contains(Mutator::TinyBird) {
+            mutators::TINY_HITBOX
+        } else {
+            1.0
+        }
+    }
+
+    /// Whether a pipe `distance` columns ahead of the bird is drawn
+    pub fn pipe_visible(&self, distance: i16) -> bool {
+        !self.contains(Mutator::InvisiblePipes)
+            || (0..=mutators::REVEAL_DISTANCE).contains(&distance)
+                && distance.rem_euclid(2 * mutators::FLASH_COLUMNS) < mutators::FLASH_COLUMNS
+    }
+
+    /// Flip what is drawn in `area` left to right, if the course is mirrored
+    pub fn mirror(&self, area: Rect, buf: &mut Buffer) {
+        if !self.contains(Mutator::Mirror) {
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            for offset in 0..area.width / 2 {
+                let (left, right) = ((area.left() + offset, y), (area.right() - 1 - offset, y));
+                let cell = buf[left].clone();
+                buf[left] = buf[right].clone();
+                buf[right] = cell;
+                for position in [left, right] {
+                    if let Some(symbol) = mirror_symbol(buf[position].symbol()) {
+                        buf[position].set_symbol(symbol);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The symbol facing the other way, for those that face one
+fn mirror_symbol(symbol: &str) -> Option<&'static str> {
+    let mirrored = match symbol {
+        "/" => "\\",
+        "\\" => "/",
+        "(" => ")",
+        ")" => "(",
+        "<" => ">",
+        ">" => "<",
+        "[" => "]",
+        "]" => "[",
+        "{" => "}",
+        "}" => "{",
+        _ => return None,
+    };
+    Some(mirrored)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutators() {
+        let mut mutators = Mutators::from(vec![Mutator::ReverseGravity, Mutator::InvisiblePipes]);
+        mutators.toggle(Mutator::FastPipes);
+        mutators.toggle(Mutator::InvisiblePipes);
+        assert_eq!(serde_json::to_string(&mutators).unwrap(), r#"["reverse_gravity","fast_pipes"]"#);
+        assert_eq!(serde_json::from_str::<Mutators>(r#"["fast_pipes","reverse_gravity"]"#).unwrap(), mutators);
+        assert_eq!((mutators.gravity(90.0), mutators.pipe_speed(12.0), mutators.hitbox()), (-90.0, 18.0, 1.0));
+        assert_eq!(mutators.labels(), "rev fast");
+
+        let invisible = Mutators::from(vec![Mutator::InvisiblePipes]);
+        let shown = (-2..=20).filter(|distance| invisible.pipe_visible(*distance)).collect::<Vec<_>>();
+        assert_eq!(shown, vec![0, 1, 2, 6, 7, 8, 12, 13, 14, 18]);
+    }
+}
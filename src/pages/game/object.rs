@@ -1,5 +1,7 @@
 use ratatui::{layout::Rect, style::Color};
 
+use crate::pages::game::animation::Animator;
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum CollisionType {
     None,
@@ -16,6 +18,23 @@ pub trait Object {
     fn get_layers(&self) -> Vec<Vec<String>>;
     fn get_colors(&self) -> Vec<Option<Color>>;
 
+    fn get_animator(&self) -> Option<&Animator> {
+        None
+    }
+
+    // Position and size of the part that can be hit, the whole object unless overridden
+    fn get_hitbox(&self) -> ((f32, f32), (u16, u16)) {
+        (self.get_pos(), self.get_size())
+    }
+
+    // Layers of the current animation frame, or the static layers if the object is not animated
+    fn get_frame_layers(&self) -> Vec<Vec<String>> {
+        match self.get_animator().and_then(|animator| animator.current_frame()) {
+            Some(frame) => frame.layers.clone(),
+            None => self.get_layers(),
+        }
+    }
+
     fn visible(&self, canvas: Rect) -> bool {
         let canvas_width = canvas.width as i16;
         let canvas_height = canvas.height as i16;
@@ -29,7 +48,38 @@ pub trait Object {
         let right = x + width - 1;
         let top = y + height - 1;
 
-        return right > 0 && x < canvas_width && top > 0 && y < canvas_height;
+        right > 0 && x < canvas_width && top > 0 && y < canvas_height
+    }
+
+    // Rasterize the non-whitespace cells of all layers into sub-cell pixels.
+    // Each cell covers `resolution` pixels, counted from the bottom-left of the canvas.
+    fn get_pixels(&self, resolution: (u16, u16)) -> Vec<(i32, i32, Color)> {
+        let (res_x, res_y) = (resolution.0 as i32, resolution.1 as i32);
+        let (_, height) = self.get_size();
+
+        let (x, y) = self.get_pos();
+        let left = (x * res_x as f32).round() as i32;
+        let bottom = (y * res_y as f32).round() as i32;
+
+        let mut pixels = Vec::new();
+        for (lines, color) in self.get_frame_layers().into_iter().zip(self.get_colors()) {
+            let color = color.unwrap_or(Color::White);
+            for (row, line) in lines.iter().enumerate() {
+                let cell_bottom = bottom + (height as i32 - 1 - row as i32) * res_y;
+                for (col, c) in line.chars().enumerate() {
+                    if c.is_whitespace() {
+                        continue;
+                    }
+                    let cell_left = left + col as i32 * res_x;
+                    for dx in 0..res_x {
+                        for dy in 0..res_y {
+                            pixels.push((cell_left + dx, cell_bottom + dy, color));
+                        }
+                    }
+                }
+            }
+        }
+        pixels
     }
 
     fn transform_pos(&self, canvas: Rect) -> (i16, i16) {
@@ -94,19 +144,16 @@ pub trait Object {
     // }
 
     fn collides_with<T: Object>(&self, other: &T) -> bool {
-        let (self_width, self_height) = self.get_size();
+        let ((self_left, self_bottom), (self_width, self_height)) = self.get_hitbox();
         let self_width = self_width as i16;
         let self_height = self_height as i16;
-
-        let (self_left, self_bottom) = self.get_pos();
         let self_left = self_left as i16;
         let self_bottom = self_bottom as i16;
 
-        let (other_width, other_height) = other.get_size();
+        let ((other_left, other_bottom), (other_width, other_height)) = other.get_hitbox();
         let other_width = other_width as i16;
         let other_height = other_height as i16;
 
-        let (other_left, other_bottom) = other.get_pos();
         let other_left = other_left as i16;
         let other_bottom = other_bottom as i16;
 
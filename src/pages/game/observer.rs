@@ -0,0 +1,28 @@
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::{
+    action::Action,
+    pages::game::{
+        course::{DeathCause, DeathSpot},
+        mutators::Mutators,
+    },
+};
+
+/// What happens to the bird of the player in a run. Times are seconds into the run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameEvent {
+    RunStarted { seed: u64, mutators: Mutators, assisted: bool, tutorial: bool, time: f32 }, /* Later than 0 if resumed */
+    Flap { time: f32 },
+    PipePassed { score: u16, time: f32 },
+    Collision { score: u16, time: f32, cause: DeathCause, spot: Option<DeathSpot> },
+    RunFinished { score: u16, crashed: bool, time: f32 },
+}
+
+/// Follows the runs of the player, e.g. to unlock achievements
+pub trait Observer {
+    fn notify(&mut self, event: &GameEvent);
+
+    #[allow(unused_variables)]
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) {
+    }
+}
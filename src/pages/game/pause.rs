@@ -0,0 +1,32 @@
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseOption {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+    Save, // And quit, to continue the run later
+}
+
+pub const PAUSE_OPTIONS: [(PauseOption, &str); 5] = [
+    (PauseOption::Resume, "Resume"),
+    (PauseOption::Restart, "Restart"),
+    (PauseOption::Settings, "Settings"),
+    (PauseOption::Quit, "Quit to Home"),
+    (PauseOption::Save, "Save and Quit"),
+];
+
+/// Whether the game is running. The bird, pipes and weather are frozen in every other state.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Pause {
+    Running,
+    Menu(usize),           // Index of the selected option
+    Countdown(SystemTime), // Start of the countdown before the game resumes
+}
+
+impl Pause {
+    pub fn running(&self) -> bool {
+        *self == Pause::Running
+    }
+}
@@ -0,0 +1,40 @@
+use crate::pages::game::bird::Bird;
+
+#[derive(Debug)]
+pub struct Player {
+    pub bird: Bird,
+    pub score: u16, // Pipes passed
+    pub alive: bool,
+    pub distance: u32, // Columns flown before being eliminated or finishing
+}
+
+impl Player {
+    pub fn new(bird: Bird) -> Self {
+        Player { bird, score: 0, alive: true, distance: 0 }
+    }
+
+    pub fn eliminate(&mut self, distance: u32) {
+        self.alive = false;
+        self.distance = distance;
+        self.bird.die();
+    }
+}
+
+/// Index of the player who got the farthest, or `None` on a tie
+pub fn winner(players: &[Player]) -> Option<usize> {
+    let best = players.iter().map(|player| (player.score, player.distance)).max()?;
+    let mut leaders = players.iter().enumerate().filter(|(_, player)| (player.score, player.distance) == best);
+    match (leaders.next(), leaders.next()) {
+        (Some((index, _)), None) => Some(index),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultOption {
+    Rematch,
+    Quit,
+}
+
+pub const RESULT_OPTIONS: [(ResultOption, &str); 2] =
+    [(ResultOption::Rematch, "Rematch"), (ResultOption::Quit, "Quit to Home")];
@@ -0,0 +1,86 @@
+use color_eyre::eyre::Result;
+use ratatui::layout::Rect;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Assist,
+    constants::game,
+    pages::game::{bird::Bird, course::Course, mutators::Mutators, object::Object},
+    utils::{get_data_dir, load_data, remove_data, save_data},
+};
+
+/// A single player run suspended mid-course, saved to `<data dir>/run.json` until it is continued
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedRun {
+    pub seed: u64,
+    pub course: Course, // With the pipes in view and the state of its generator
+    pub bird: Bird,
+    pub score: u16,
+    pub time: f32, // Seconds into the run
+    pub mutators: Mutators,
+    pub assist: Assist,
+}
+
+impl SavedRun {
+    pub fn exists() -> bool {
+        get_data_dir().join(game::SAVE_FILE).exists()
+    }
+
+    pub fn load() -> Option<Self> {
+        load_data(game::SAVE_FILE)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data(game::SAVE_FILE, self)
+    }
+
+    /// A run is only continued once
+    pub fn remove() -> Result<()> {
+        remove_data(game::SAVE_FILE)
+    }
+
+    /// Whether the run was saved on a canvas of the size of `canvas`, with the bird inside it
+    pub fn fits(&self, canvas: Rect) -> bool {
+        let (x, y) = self.bird.get_pos();
+        let (width, height) = self.bird.get_size();
+        self.course.size() == (canvas.width, canvas.height)
+            && x >= 0.0
+            && y >= 0.0
+            && x + width as f32 <= canvas.width as f32
+            && y + height as f32 <= canvas.height as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saved_run() {
+        let mut course = Course::new(42, 80, 30);
+        for _ in 0..150 {
+            course.advance(10);
+        }
+        let bird = Bird::new(vec!["(o>"], vec![None], 10, 12, 30.0);
+        let run = SavedRun {
+            seed: 42,
+            course,
+            bird,
+            score: 2,
+            time: 9.5,
+            mutators: Mutators::default(),
+            assist: Assist::default(),
+        };
+        let mut loaded: SavedRun = serde_json::from_str(&serde_json::to_string(&run).unwrap()).unwrap();
+        assert!(loaded.fits(Rect::new(0, 0, 80, 30)) && !loaded.fits(Rect::new(0, 0, 100, 30)));
+
+        // The course carries on with the same pipes
+        let mut course = run.course;
+        for _ in 0..150 {
+            assert_eq!(course.advance(10), loaded.course.advance(10));
+        }
+        let pipes = |course: &Course| course.pipes.iter().map(|(lower, _)| lower.get_pos()).collect::<Vec<_>>();
+        assert_eq!(pipes(&course), pipes(&loaded.course));
+        assert_eq!(serde_json::to_string(&course).unwrap(), serde_json::to_string(&loaded.course).unwrap());
+    }
+}
@@ -0,0 +1,340 @@
+use std::io::{BufRead, Write};
+
+use color_eyre::eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::WindConfig,
+    constants::game,
+    pages::game::{bird::Bird, course::Course, mutators::Mutators, object::Object, weather::Weather},
+};
+
+/// Bumped on every incompatible change of the JSON protocol
+pub const PROTOCOL_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Move {
+    Idle,
+    Flap,
+}
+
+/// What an agent sees after each step. Heights are in rows from the floor.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Observation {
+    pub y: f32,
+    pub velocity: f32,
+    pub pipe_distance: Option<f32>, // Columns from the bird to the next pipe
+    pub gap_bottom: Option<f32>,
+    pub gap_top: Option<f32>,
+    pub score: u16,
+    pub distance: u32, // Columns flown
+}
+
+/// The game without a terminal, advancing a fixed time per step. The game on screen takes the same steps.
+#[derive(Debug)]
+pub struct Simulation {
+    width: u16,
+    height: u16,
+    bird: Bird,
+    course: Course,
+    weather: Weather,
+    score: u16,
+    scroll_time: f32, // Seconds since the course last scrolled
+    done: bool,
+    mutators: Mutators,
+}
+
+impl Simulation {
+    pub fn new(width: u16, height: u16) -> Self {
+        let mut simulation = Simulation {
+            width,
+            height,
+            bird: Self::create_bird(height, Mutators::default()),
+            course: Course::new(0, width, height),
+            weather: Weather::new(WindConfig::default()),
+            score: 0,
+            scroll_time: 0.0,
+            done: false,
+            mutators: Mutators::default(),
+        };
+        simulation.reset(0);
+        simulation
+    }
+
+    /// Fly with `mutators` from the next reset on
+    pub fn mutators(self, mutators: Mutators) -> Self {
+        Simulation { mutators, ..self }
+    }
+
+    /// Fly in the gusts of `wind`, drawn from the seed of the course
+    pub fn wind(mut self, wind: WindConfig) -> Self {
+        self.weather.set_wind(wind);
+        self
+    }
+
+    fn create_bird(height: u16, mutators: Mutators) -> Bird {
+        let (layers, colors) = (game::BIRD_TEXTS.to_vec(), game::BIRD_COLORS.to_vec());
+        let y = Self::start_y(height) as u16;
+        Bird::new(layers, colors, game::BIRD_INITIAL_X, y, game::VELOCITY_LIMIT).hitbox(mutators.hitbox())
+    }
+
+    /// Row the bird starts every run at, in the middle of the course, which runs on screen start at to be replayed
+    pub fn start_y(height: u16) -> f32 {
+        (height / 2) as f32
+    }
+
+    /// Columns and rows of the bird, which runs on screen have to match to be replayed
+    pub fn bird_size() -> (u16, u16) {
+        Self::create_bird(0, Mutators::default()).get_size()
+    }
+
+    /// Start over on the course drawn from `seed`
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        self.bird = Self::create_bird(self.height, self.mutators);
+        self.course = Course::new(seed, self.width, self.height);
+        self.weather.seed(seed);
+        self.weather.reset();
+        self.score = 0;
+        self.scroll_time = 0.0;
+        self.done = false;
+        self.observe()
+    }
+
+    /// Advance by one step, and return what the bird sees, the reward and whether the run is over
+    pub fn step(&mut self, action: Move) -> (Observation, f32, bool) {
+        if self.done {
+            return (self.observe(), 0.0, true);
+        }
+        if action == Move::Flap {
+            self.bird.up(self.mutators.up_velocity());
+        }
+        // In the order of the game on screen: the wind, the course, then the bird
+        self.weather.step(game::SIM_STEP);
+
+        let mut reward = game::SIM_SURVIVAL_REWARD;
+        self.scroll_time += game::SIM_STEP;
+        let scroll_interval = 1.0 / (self.mutators.pipe_speed(game::PIPE_VELOCITY) * (1.0 - self.weather.drag()));
+        while self.scroll_time >= scroll_interval {
+            self.scroll_time -= scroll_interval;
+            if self.course.advance(game::BIRD_INITIAL_X as i16) {
+                self.score += 1;
+                reward += game::SIM_PIPE_REWARD;
+            }
+        }
+        self.bird.step(self.mutators.gravity(game::GRAVITY - self.weather.lift()), game::SIM_STEP);
+
+        if self.course.collides(&self.bird) {
+            self.done = true;
+            reward = game::SIM_CRASH_REWARD;
+        } else if self.course.finished() {
+            self.done = true;
+        }
+        (self.observe(), reward, self.done)
+    }
+
+    fn observe(&self) -> Observation {
+        let (x, y) = self.bird.get_pos();
+        let next_pipe = self.course.pipes.iter().find(|(lower, _)| lower.get_pos().0 + lower.get_size().0 as f32 >= x);
+        Observation {
+            y,
+            velocity: self.bird.velocity(),
+            pipe_distance: next_pipe.map(|(lower, _)| lower.get_pos().0 - x),
+            gap_bottom: next_pipe.map(|(lower, _)| lower.get_pos().1 + lower.get_size().1 as f32),
+            gap_top: next_pipe.map(|(_, upper)| upper.get_pos().1),
+            score: self.score,
+            distance: self.course.scroll,
+        }
+    }
+}
+
+/// The inputs of a run, enough to fly it again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub width: u16,
+    pub height: u16,
+    pub flaps: Vec<u32>, // Steps the bird flapped at, in order
+    #[serde(default)]
+    pub mutators: Mutators,
+    #[serde(default)]
+    pub wind: WindConfig,
+}
+
+impl Replay {
+    pub fn new(seed: u64, width: u16, height: u16) -> Self {
+        Replay { seed, width, height, flaps: Vec::new(), mutators: Mutators::default(), wind: WindConfig::default() }
+    }
+
+    pub fn mutators(self, mutators: Mutators) -> Self {
+        Replay { mutators, ..self }
+    }
+
+    pub fn wind(self, wind: WindConfig) -> Self {
+        Replay { wind, ..self }
+    }
+
+    /// Fly the run again, and return the pipes passed and the columns flown
+    pub fn play(&self) -> Result<(u16, u32)> {
+        check_size(self.width, self.height)?;
+        if self.flaps.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(eyre!("Flaps out of order"));
+        }
+        let WindConfig { interval, duration, lift, drag } = self.wind;
+        if [interval, duration, lift, drag].iter().any(|value| !value.is_finite() || *value < 0.0) {
+            return Err(eyre!("Unsupported wind"));
+        }
+
+        let mut simulation = Simulation::new(self.width, self.height).mutators(self.mutators).wind(self.wind);
+        simulation.reset(self.seed);
+        let mut flaps = self.flaps.iter().peekable();
+        for step in 0..game::REPLAY_MAX_STEPS {
+            let action = if flaps.next_if_eq(&&step).is_some() { Move::Flap } else { Move::Idle };
+            let (observation, _, done) = simulation.step(action);
+            if done {
+                return Ok((observation.score, observation.distance));
+            }
+        }
+        Err(eyre!("The run is too long"))
+    }
+}
+
+/// Writes down the flaps of a run on screen as steps of the simulation
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    replay: Replay,
+}
+
+impl Recorder {
+    pub fn new(replay: Replay) -> Self {
+        Recorder { replay }
+    }
+
+    /// A flap before step `step` of the run
+    pub fn flap(&mut self, step: u32) {
+        if self.replay.flaps.last() != Some(&step) {
+            self.replay.flaps.push(step);
+        }
+    }
+
+    pub fn finish(self) -> Replay {
+        self.replay
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "cmd", rename_all = "snake_case")]
+enum Request {
+    Reset { seed: Option<u64> },
+    Step { action: Move },
+    Close,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Response {
+    Hello { version: u32, width: u16, height: u16, dt: f32 },
+    Reset { observation: Observation },
+    Step { observation: Observation, reward: f32, done: bool },
+    Error { error: String },
+}
+
+impl Simulation {
+    /// Answer one line of the protocol, or return `None` once the client closes
+    fn respond(&mut self, line: &str) -> Option<Response> {
+        let response = match serde_json::from_str::<Request>(line) {
+            Ok(Request::Reset { seed }) => {
+                Response::Reset { observation: self.reset(seed.unwrap_or_else(rand::random)) }
+            },
+            Ok(Request::Step { action }) => {
+                let (observation, reward, done) = self.step(action);
+                Response::Step { observation, reward, done }
+            },
+            Ok(Request::Close) => return None,
+            Err(e) => Response::Error { error: e.to_string() },
+        };
+        Some(response)
+    }
+}
+
+/// Courses are only drawn in sizes that leave room for the gaps and the bird
+fn check_size(width: u16, height: u16) -> Result<()> {
+    let (min_width, min_height) = game::REPLAY_MIN_SIZE;
+    let (max_width, max_height) = game::REPLAY_MAX_SIZE;
+    if !(min_width..=max_width).contains(&width) || !(min_height..=max_height).contains(&height) {
+        return Err(eyre!("Unsupported course size {width}x{height}"));
+    }
+    Ok(())
+}
+
+/// Speak line-delimited JSON over stdin and stdout until the client closes
+pub fn serve(width: u16, height: u16) -> Result<()> {
+    check_size(width, height)?;
+    let mut simulation = Simulation::new(width, height);
+    let mut stdout = std::io::stdout().lock();
+    let hello = Response::Hello { version: PROTOCOL_VERSION, width, height, dt: game::SIM_STEP };
+    writeln!(stdout, "{}", serde_json::to_string(&hello)?)?;
+    stdout.flush()?;
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some(response) = simulation.respond(&line) else {
+            break;
+        };
+        writeln!(stdout, "{}", serde_json::to_string(&response)?)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{HEIGHT, WIDTH};
+
+    #[test]
+    fn test_simulation_is_deterministic() {
+        let mut first = Simulation::new(WIDTH, HEIGHT);
+        let mut second = Simulation::new(WIDTH, HEIGHT);
+        assert_eq!(first.reset(42), second.reset(42));
+        for index in 0..200 {
+            let action = if index % 9 == 0 { Move::Flap } else { Move::Idle };
+            assert_eq!(first.step(action), second.step(action));
+        }
+
+        // Without flapping the bird falls to the floor
+        first.reset(42);
+        let (observation, reward, done) =
+            std::iter::repeat_with(|| first.step(Move::Idle)).find(|(_, _, done)| *done).unwrap();
+        assert_eq!(reward, game::SIM_CRASH_REWARD);
+        assert!(observation.y < 1.0);
+    }
+
+    #[test]
+    fn test_replay() -> Result<()> {
+        let mut replay = Replay::new(42, WIDTH, HEIGHT);
+        let (score, distance) = replay.play()?;
+        assert_eq!(score, 0);
+        assert!(distance > 0);
+
+        replay.flaps = vec![10, 5];
+        assert!(replay.play().is_err());
+        replay.flaps = vec![5, 10];
+        replay.height = 2;
+        assert!(replay.play().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_protocol() {
+        let mut simulation = Simulation::new(WIDTH, HEIGHT);
+        let response = serde_json::to_string(&simulation.respond(r#"{"cmd":"step","action":"flap"}"#)).unwrap();
+        assert!(response.starts_with(r#"{"observation":{"y":"#) && response.ends_with(r#""reward":0.1,"done":false}"#));
+        assert!(matches!(simulation.respond(r#"{"cmd":"jump"}"#), Some(Response::Error { .. })));
+        assert!(simulation.respond(r#"{"cmd":"close"}"#).is_none());
+        assert!(serve(WIDTH, 5).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use color_eyre::eyre::Result;
+use ratatui::{style::Stylize, text::Line};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::{game, speedrun},
+    utils::{load_data, save_data},
+};
+
+/// Times of the personal best at every split, saved to `<data dir>/splits.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Splits(pub Vec<f32>);
+
+impl Splits {
+    pub fn load() -> Self {
+        load_data(speedrun::FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data(speedrun::FILE, self)
+    }
+
+    fn complete(&self) -> bool {
+        self.0.len() == (game::MAX_PIPE_NUM / speedrun::SPLIT_PIPES) as usize
+    }
+}
+
+/// Splits of a run up to the last pipe, against the personal best
+#[derive(Debug, Clone, Default)]
+pub struct Speedrun {
+    splits: Splits,
+    best: Splits,
+    finish: Option<f32>,
+}
+
+impl Speedrun {
+    pub fn new(best: Splits) -> Self {
+        Speedrun { splits: Splits::default(), best, finish: None }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.finish.is_some()
+    }
+
+    /// Take the split of `score` if there is one, and return whether the run set a new personal best
+    pub fn pipe_passed(&mut self, score: u16, time: f32) -> bool {
+        if self.finished() || !score.is_multiple_of(speedrun::SPLIT_PIPES) {
+            return false;
+        }
+        self.splits.0.push(time);
+        if score < game::MAX_PIPE_NUM {
+            return false;
+        }
+        self.finish = Some(time);
+        !self.best.complete() || self.best.0.last().is_some_and(|best| time < *best)
+    }
+
+    pub fn splits(&self) -> &Splits {
+        &self.splits
+    }
+
+    /// The timer at `time`, stopped at the last pipe, and every split taken with its delta to the personal best
+    pub fn lines(&self, time: f32) -> Vec<Line<'static>> {
+        let mut lines = vec![Line::from(format_time(self.finish.unwrap_or(time))).bold()];
+        for (index, split) in self.splits.0.iter().enumerate() {
+            let pipes = (index as u16 + 1) * speedrun::SPLIT_PIPES;
+            let mut line = Line::from(format!("{pipes:>2} {}", format_time(*split)));
+            if let Some(best) = self.best.0.get(index) {
+                let delta = split - best;
+                let color = if delta <= 0.0 { speedrun::AHEAD_COLOR } else { speedrun::BEHIND_COLOR };
+                line.push_span(format!(" {delta:+.2}").fg(color));
+            }
+            lines.push(line);
+        }
+        lines
+    }
+}
+
+/// Minutes, seconds and milliseconds
+fn format_time(time: f32) -> String {
+    let millis = (time * 1000.0).round() as u32;
+    format!("{}:{:02}.{:03}", millis / 60000, millis / 1000 % 60, millis % 1000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splits() {
+        let mut speedrun = Speedrun::new(Splits(vec![10.0, 20.0, 30.0, 40.0]));
+        assert!(!speedrun.pipe_passed(4, 8.0));
+        let times = [(5, 9.5), (10, 20.5), (15, 29.0)];
+        assert!(times.iter().all(|&(score, time)| !speedrun.pipe_passed(score, time)));
+        assert_eq!(speedrun.splits.0, vec![9.5, 20.5, 29.0]);
+        assert_eq!(speedrun.lines(35.0)[0].to_string(), "0:35.000");
+        assert_eq!(speedrun.lines(35.0)[2].to_string(), "10 0:20.500 +0.50");
+
+        // Finishing faster sets a new best and stops the timer
+        assert!(speedrun.pipe_passed(20, 39.25));
+        assert_eq!(speedrun.splits().0, vec![9.5, 20.5, 29.0, 39.25]);
+        assert_eq!(speedrun.lines(45.0)[0].to_string(), "0:39.250");
+        assert_eq!(speedrun.lines(45.0)[4].to_string(), "20 0:39.250 -0.75");
+        assert!(!speedrun.pipe_passed(20, 50.0));
+    }
+}
@@ -0,0 +1,128 @@
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{constants::game, utils};
+
+const TUTORIAL_FILE: &str = "tutorial.json";
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct TutorialRecord {
+    seen: bool,
+}
+
+pub fn tutorial_seen() -> bool {
+    utils::load_data::<TutorialRecord>(TUTORIAL_FILE).is_some_and(|record| record.seen)
+}
+
+pub fn mark_tutorial_seen() {
+    if let Err(e) = utils::save_data(TUTORIAL_FILE, &TutorialRecord { seen: true }) {
+        log::error!("Failed to save the tutorial record: {e}");
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TutorialStep {
+    Flap,     // Until the player has flapped a few times
+    Goal,     // Until the bird passes the first pipe
+    LastPipe, // For a while
+    Done,
+}
+
+/// What a callout points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalloutTarget {
+    Bird,
+    NextPipe,
+    Nothing,
+}
+
+#[derive(Debug)]
+pub struct Tutorial {
+    step: TutorialStep,
+    flaps: u16,
+    step_start: SystemTime,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Tutorial { step: TutorialStep::Flap, flaps: 0, step_start: SystemTime::now() }
+    }
+
+    pub fn step(&self) -> TutorialStep {
+        self.step
+    }
+
+    pub fn finished(&self) -> bool {
+        self.step == TutorialStep::Done
+    }
+
+    fn next(&mut self) {
+        self.step = match self.step {
+            TutorialStep::Flap => TutorialStep::Goal,
+            TutorialStep::Goal => TutorialStep::LastPipe,
+            TutorialStep::LastPipe | TutorialStep::Done => TutorialStep::Done,
+        };
+        self.step_start = SystemTime::now();
+        if self.finished() {
+            mark_tutorial_seen();
+        }
+    }
+
+    pub fn on_flap(&mut self) {
+        self.flaps += 1;
+        if self.step == TutorialStep::Flap && self.flaps >= game::TUTORIAL_FLAPS {
+            self.next();
+        }
+    }
+
+    pub fn on_pipe_passed(&mut self) {
+        if self.step == TutorialStep::Goal {
+            self.next();
+        }
+    }
+
+    pub fn update(&mut self) {
+        let elapsed = self.step_start.elapsed().unwrap_or_default().as_secs_f32();
+        if self.step == TutorialStep::LastPipe && elapsed >= game::TUTORIAL_STEP_DURATION {
+            self.next();
+        }
+    }
+
+    /// Text of the current callout, with `key` as the flap key
+    pub fn callout(&self, key: &str) -> Option<(String, CalloutTarget)> {
+        let callout = match self.step {
+            TutorialStep::Flap => (format!("Press {key} to flap.\nKeep the bird in the air!"), CalloutTarget::Bird),
+            TutorialStep::Goal => {
+                (
+                    format!("Fly through the gaps.\nClear all {} pipes to win!", game::MAX_PIPE_NUM),
+                    CalloutTarget::NextPipe,
+                )
+            },
+            TutorialStep::LastPipe => {
+                ("The cyan pipe is the last one.\nPass it to open your card!".to_string(), CalloutTarget::Nothing)
+            },
+            TutorialStep::Done => return None,
+        };
+        Some(callout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_steps() {
+        let mut tutorial = Tutorial { step: TutorialStep::Flap, flaps: 0, step_start: SystemTime::now() };
+        tutorial.on_pipe_passed();
+        assert_eq!(tutorial.step(), TutorialStep::Flap);
+        for _ in 0..game::TUTORIAL_FLAPS {
+            tutorial.on_flap();
+        }
+        assert_eq!(tutorial.step(), TutorialStep::Goal);
+        tutorial.on_pipe_passed();
+        assert_eq!(tutorial.step(), TutorialStep::LastPipe);
+        assert_eq!(tutorial.callout("Space").unwrap().1, CalloutTarget::Nothing);
+    }
+}
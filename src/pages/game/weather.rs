@@ -0,0 +1,144 @@
+use std::f32::consts::PI;
+
+use rand::{prelude::*, rngs::StdRng};
+
+use crate::config::WindConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GustKind {
+    Updraft,
+    Downdraft,
+    Headwind,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Gust {
+    kind: GustKind,
+    elapsed: f32,
+}
+
+/// Gusts of wind drawn from a seed, advanced a fixed step at a time
+#[derive(Debug)]
+pub struct Weather {
+    wind: WindConfig,
+    gust: Option<Gust>,
+    until_next: f32, // Seconds until the next gust
+    rng: StdRng,
+}
+
+impl Weather {
+    pub fn new(wind: WindConfig) -> Self {
+        let mut weather = Weather { wind, gust: None, until_next: 0.0, rng: StdRng::from_entropy() };
+        weather.reset();
+        weather
+    }
+
+    pub fn set_wind(&mut self, wind: WindConfig) {
+        self.wind = wind;
+    }
+
+    pub fn wind(&self) -> WindConfig {
+        self.wind
+    }
+
+    /// Draw the same gusts from `seed` after the next reset
+    pub fn seed(&mut self, seed: u64) {
+        self.rng = StdRng::seed_from_u64(seed);
+    }
+
+    pub fn reset(&mut self) {
+        self.gust = None;
+        self.until_next = self.sample_interval();
+    }
+
+    fn sample_interval(&mut self) -> f32 {
+        self.wind.interval * self.rng.gen_range(0.5..1.5)
+    }
+
+    /// Advance the gusts by `dt` seconds
+    pub fn step(&mut self, dt: f32) {
+        match self.gust.as_mut() {
+            Some(gust) => {
+                gust.elapsed += dt;
+                if gust.elapsed >= self.wind.duration {
+                    self.gust = None;
+                    self.until_next = self.sample_interval();
+                }
+            },
+            None if self.wind.interval > 0.0 => {
+                self.until_next -= dt;
+                if self.until_next <= 0.0 {
+                    let kind =
+                        *[GustKind::Updraft, GustKind::Downdraft, GustKind::Headwind].choose(&mut self.rng).unwrap();
+                    self.gust = Some(Gust { kind, elapsed: 0.0 });
+                }
+            },
+            None => {},
+        }
+    }
+
+    /// Strength of the current gust from 0 to 1. It rises and falls over the duration of the gust.
+    fn intensity(&self) -> f32 {
+        match self.gust {
+            Some(gust) if self.wind.duration > 0.0 => (PI * gust.elapsed / self.wind.duration).sin().max(0.0),
+            _ => 0.0,
+        }
+    }
+
+    pub fn gust(&self) -> Option<GustKind> {
+        self.gust.map(|gust| gust.kind)
+    }
+
+    /// Vertical acceleration on the bird: rows per second squared
+    pub fn lift(&self) -> f32 {
+        match self.gust() {
+            Some(GustKind::Updraft) => self.wind.lift * self.intensity(),
+            Some(GustKind::Downdraft) => -self.wind.lift * self.intensity(),
+            _ => 0.0,
+        }
+    }
+
+    /// Fraction of the pipe speed taken by a headwind
+    pub fn drag(&self) -> f32 {
+        match self.gust() {
+            Some(GustKind::Headwind) => (self.wind.drag * self.intensity()).clamp(0.0, 0.9),
+            _ => 0.0,
+        }
+    }
+
+    /// Direction and intensity of the wind in world coordinates, for the visuals
+    pub fn direction(&self) -> (f32, f32) {
+        let intensity = self.intensity();
+        match self.gust() {
+            Some(GustKind::Updraft) => (0.0, intensity),
+            Some(GustKind::Downdraft) => (0.0, -intensity),
+            Some(GustKind::Headwind) => (-intensity, 0.0),
+            None => (0.0, 0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gust_rises_and_falls() {
+        let wind = WindConfig { interval: 1.0, duration: 2.0, lift: 40.0, drag: 0.5 };
+        let mut weather = Weather::new(wind);
+        assert_eq!(weather.direction(), (0.0, 0.0));
+
+        for kind in [GustKind::Updraft, GustKind::Downdraft, GustKind::Headwind] {
+            weather.gust = Some(Gust { kind, elapsed: 1.0 });
+            let (lift, drag) = (weather.lift(), weather.drag());
+            match kind {
+                GustKind::Updraft => assert_eq!((lift, drag), (40.0, 0.0)),
+                GustKind::Downdraft => assert_eq!((lift, drag), (-40.0, 0.0)),
+                GustKind::Headwind => assert_eq!((lift, drag), (0.0, 0.5)),
+            }
+
+            weather.gust = Some(Gust { kind, elapsed: 0.0 });
+            assert_eq!(weather.direction(), (0.0, 0.0));
+        }
+    }
+}
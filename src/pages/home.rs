@@ -1,4 +1,7 @@
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime},
+};
 
 use color_eyre::eyre::Result;
 use crossterm::event::{KeyCode, KeyEvent};
@@ -12,17 +15,32 @@ use tui_input::{backend::crossterm::EventHandler, Input};
 use super::{Frame, Page, PageId};
 use crate::{
     action::{act, Action, ActionState, Command, HomeAction},
+    assets,
     components::{
         background::{Background, BackgroundState},
         multiline::MultiLine,
     },
-    config::{key_event_to_string, PageKeyBindings},
-    constants::title,
+    config::{key_event_to_string, Config, PageKeyBindings, Settings},
+    constants::home,
+    daily::DailyRecord,
+    pages::game::save::SavedRun,
+    skin::Skin,
+    utils::local_day,
 };
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 enum OptionItem {
+    Continue,
     Start,
+    Daily,
+    Race,
+    Speedrun,
+    Tournament,
+    Scores,
+    Achievements,
+    Stats,
+    Tutorial,
+    Skin,
 }
 
 pub struct HomePage {
@@ -30,17 +48,107 @@ pub struct HomePage {
     pub keymap: PageKeyBindings,
     options: Vec<(OptionItem, &'static str)>,
     selected_option_index: usize,
+    settings: Settings,
+    skins: Vec<Skin>,
+    selected_skin_index: usize,
+    last_input: SystemTime,
+    demo: bool,
+    daily: DailyRecord,
     // background_state: BackgroundState,
 }
 
 impl HomePage {
     pub fn new() -> Self {
-        HomePage {
+        let mut home = HomePage {
             action_tx: None,
             keymap: PageKeyBindings::default(),
-            options: vec![(OptionItem::Start, "Start playing")],
+            options: vec![
+                (OptionItem::Start, "Start playing"),
+                (OptionItem::Daily, "Daily Challenge"),
+                (OptionItem::Race, "Two-player race"),
+                (OptionItem::Speedrun, "Speedrun"),
+                (OptionItem::Tournament, "Tournament"),
+                (OptionItem::Scores, "Global Scores"),
+                (OptionItem::Achievements, "Achievements"),
+                (OptionItem::Stats, "Statistics"),
+                (OptionItem::Tutorial, "How to play"),
+                (OptionItem::Skin, "Skin"),
+            ],
             selected_option_index: 0,
+            settings: Settings::default(),
+            skins: Skin::builtin(),
+            selected_skin_index: 0,
+            last_input: SystemTime::now(),
+            demo: false,
+            daily: DailyRecord::load(),
             // background_state: BackgroundState::new(2.0, 1.0 / 30.0).show_tree().show_snowman(),
+        };
+        home.refresh_continue();
+        home
+    }
+
+    /// Offer to continue the run saved, first, as long as there is one
+    fn refresh_continue(&mut self) {
+        let shown = self.options.first().is_some_and(|(item, _)| *item == OptionItem::Continue);
+        match (SavedRun::exists(), shown) {
+            (true, false) => self.options.insert(0, (OptionItem::Continue, "Continue")),
+            (false, true) => {
+                self.options.remove(0);
+                self.selected_option_index = self.selected_option_index.min(self.options.len() - 1);
+            },
+            _ => {},
+        }
+    }
+
+    fn idle(&self) -> bool {
+        self.last_input.elapsed().unwrap_or_default().as_secs_f32() >= home::ATTRACT_DELAY
+    }
+
+    fn selected_option(&self) -> OptionItem {
+        self.options[self.selected_option_index].0
+    }
+
+    fn cycle_skin(&mut self, step: isize) -> Result<()> {
+        let num_skins = self.skins.len() as isize;
+        self.selected_skin_index = (self.selected_skin_index as isize + step).rem_euclid(num_skins) as usize;
+
+        let settings = Settings { skin: self.skins[self.selected_skin_index].name.clone(), ..self.settings.clone() };
+        if let Some(action_tx) = &self.action_tx {
+            action_tx.send(act!(Command::UpdateSettings(settings)))?;
+        }
+        Ok(())
+    }
+
+    fn option_title(&self, item: OptionItem, title: &str) -> String {
+        match item {
+            OptionItem::Skin => format!("{title}: < {} >", self.skins[self.selected_skin_index].name),
+            OptionItem::Daily => {
+                let day = local_day();
+                match (self.daily.played(day), self.daily.streak(day)) {
+                    (true, streak) => format!("{title}: {} pipes, {streak} day streak", self.daily.last_score),
+                    (false, 0) => title.to_string(),
+                    (false, streak) => format!("{title}: {streak} day streak"),
+                }
+            },
+            _ => title.to_string(),
+        }
+    }
+
+    fn draw_skin_preview(&self, f: &mut Frame<'_>, area: Rect) {
+        let skin = &self.skins[self.selected_skin_index];
+        let (width, height) = skin.size();
+
+        let [_, area, _] =
+            Layout::horizontal(vec![Constraint::Fill(1), Constraint::Length(width), Constraint::Fill(1)]).areas(area);
+        let [area] = Layout::vertical(vec![Constraint::Length(height)]).flex(layout::Flex::Center).areas(area);
+
+        for (index, (layer, color)) in skin.layers.iter().zip(skin.colors.iter()).enumerate() {
+            let lines: Vec<&str> = layer.lines().filter(|line| !line.is_empty()).collect();
+            let lines = MultiLine::new(lines).ignore_whitespace(index > 0);
+            match color {
+                Some(color) => f.render_widget(lines.style(Style::default().fg(*color)), area),
+                None => f.render_widget(lines, area),
+            }
         }
     }
 
@@ -58,7 +166,8 @@ impl HomePage {
 
     fn draw_options(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
         // Draw options
-        let option_titles = self.options.iter().map(|(_, title)| *title).collect::<Vec<_>>();
+        let option_titles =
+            self.options.iter().map(|(item, title)| self.option_title(*item, title)).collect::<Vec<_>>();
         let max_option_len = option_titles.iter().map(|title| title.len()).max().unwrap_or(0) as u16;
         let num_option_titles = option_titles.len();
 
@@ -79,7 +188,6 @@ impl HomePage {
 
         let lines = MultiLine::new(option_titles).line_padding(1).line_styles(
             (0..num_option_titles)
-                .into_iter()
                 .map(|index| {
                     let selected = index == self.selected_option_index;
                     match selected {
@@ -112,21 +220,101 @@ impl Page for HomePage {
         Ok(())
     }
 
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.skins = Skin::load_all();
+        self.selected_skin_index =
+            self.skins.iter().position(|skin| skin.name == config.settings.skin).unwrap_or_default();
+        self.settings = config.settings;
+        Ok(())
+    }
+
     fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
         // TODO: Handle keymap
+        self.last_input = SystemTime::now();
         Ok(None)
     }
 
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action.command {
+            Command::Tick if !self.demo && self.idle() => {
+                self.demo = true;
+                if let Some(action_tx) = &self.action_tx {
+                    action_tx.send(act!(Command::StartDemo))?;
+                }
+            },
+            Command::StopDemo | Command::ShowHome => {
+                self.demo = false;
+                self.daily = DailyRecord::load();
+                self.refresh_continue();
+                self.last_input = SystemTime::now();
+            },
+            _ => {},
+        }
+
         if let Command::Home(command) = action.command {
             match command {
                 HomeAction::Up => self.up(),
                 HomeAction::Down => self.down(),
+                HomeAction::Left if self.selected_option() == OptionItem::Skin => self.cycle_skin(-1)?,
+                HomeAction::Right if self.selected_option() == OptionItem::Skin => self.cycle_skin(1)?,
                 HomeAction::Select => {
-                    if let Some(action_tx) = &self.action_tx {
-                        action_tx.send(act!(Command::StartGame))?;
+                    match self.selected_option() {
+                        OptionItem::Continue => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::ContinueRun))?;
+                            }
+                        },
+                        OptionItem::Start => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::OpenMutators))?;
+                            }
+                        },
+                        // One attempt per day
+                        OptionItem::Daily if !self.daily.played(local_day()) => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::StartDaily(local_day())))?;
+                            }
+                        },
+                        OptionItem::Daily => {},
+                        OptionItem::Race => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::StartRace))?;
+                            }
+                        },
+                        OptionItem::Speedrun => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::StartSpeedrun))?;
+                            }
+                        },
+                        OptionItem::Tournament => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::OpenTournament))?;
+                            }
+                        },
+                        OptionItem::Scores => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::OpenScores))?;
+                            }
+                        },
+                        OptionItem::Achievements => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::OpenAchievements))?;
+                            }
+                        },
+                        OptionItem::Stats => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::OpenStats))?;
+                            }
+                        },
+                        OptionItem::Tutorial => {
+                            if let Some(action_tx) = &self.action_tx {
+                                action_tx.send(act!(Command::StartTutorial))?;
+                            }
+                        },
+                        OptionItem::Skin => self.cycle_skin(1)?,
                     }
                 },
+                _ => {},
             }
         }
         Ok(None)
@@ -137,40 +325,43 @@ impl Page for HomePage {
         // let background = Background::default();
         // f.render_stateful_widget(background, area, &mut self.background_state);
 
-        let title_lines: Vec<&str> = title::TITLE_TEXT.lines().filter(|s| s.len() != 0).collect();
-        let shadow_lines: Vec<&str> = title::TITLE_SHADOW.lines().filter(|s| s.len() != 0).collect();
-
-        let num_title_lines = title_lines.len().max(shadow_lines.len()) as u16;
+        let title = assets::get(assets::TITLE);
+        let (width, num_title_lines) = title.size();
 
         let num_options = self.options.len() as u16;
         let option_height = num_options * 2 - 1;
 
-        let [title_area, option_area] =
-            Layout::vertical(vec![Constraint::Length(num_title_lines), Constraint::Length(option_height)])
-                .flex(layout::Flex::SpaceAround)
-                .areas(area);
+        let preview_height =
+            self.skins.iter().map(|skin| skin.size().1).max().unwrap_or(0) + home::PREVIEW_VPADDING * 2;
 
-        // Draw title
-        let title_width = title_lines.iter().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
-        let shadow_width = shadow_lines.iter().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
-        let width = title_width.max(shadow_width);
+        let [title_area, option_area, preview_area] = Layout::vertical(vec![
+            Constraint::Length(num_title_lines),
+            Constraint::Length(option_height),
+            Constraint::Length(preview_height),
+        ])
+        .flex(layout::Flex::SpaceAround)
+        .areas(area);
 
+        // Draw title
         let [_, title_area, _] =
             Layout::horizontal(vec![Constraint::Fill(1), Constraint::Length(width), Constraint::Fill(1)])
                 .flex(layout::Flex::SpaceAround)
                 .areas(title_area);
-
-        let shadow_lines =
-            MultiLine::new(shadow_lines).ignore_whitespace(true).pixel_mode().style(Style::default().fg(Color::Green));
-        f.render_widget(shadow_lines, title_area);
-
-        let title_lines =
-            MultiLine::new(title_lines).ignore_whitespace(true).pixel_mode().style(Style::default().fg(Color::Red));
-        f.render_widget(title_lines, title_area);
+        let title_area = title_area.offset(title.offset());
+        for (index, lines) in title.lines().into_iter().enumerate() {
+            let lines = MultiLine::new(lines)
+                .ignore_whitespace(true)
+                .pixel_mode()
+                .style(Style::default().fg(title.color(index)));
+            f.render_widget(lines, title_area);
+        }
 
         // Draw options
         self.draw_options(f, option_area)?;
 
+        // Draw the selected skin
+        self.draw_skin_preview(f, preview_area);
+
         Ok(())
     }
 
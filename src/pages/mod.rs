@@ -9,19 +9,33 @@ use tokio::sync::mpsc::UnboundedSender;
 
 use crate::{
     action::Action,
+    components::background::BackgroundState,
     config::{Config, PageKeyBindings},
+    net::Session,
     tui::Event,
 };
 
+pub mod achievements;
 pub mod card;
 pub mod game;
 pub mod home;
+pub mod mutators;
+pub mod scores;
+pub mod settings;
+pub mod stats;
+pub mod tournament;
 
 #[derive(Debug, Deserialize, Hash, Eq, PartialEq, Clone, Display)]
 pub enum PageId {
     Home,
     Game,
     Card,
+    Settings,
+    Tournament,
+    Scores,
+    Achievements,
+    Stats,
+    Mutators,
 }
 
 pub trait Page {
@@ -37,9 +51,18 @@ pub trait Page {
     fn register_config_handler(&mut self, config: Config) -> Result<()> {
         Ok(())
     }
+    #[allow(unused_variables)]
+    fn register_session_handler(&mut self, session: Session) -> Result<()> {
+        Ok(())
+    }
     fn init(&mut self) -> Result<()> {
         Ok(())
     }
+    /// Whether key presses are typed into the page instead of being mapped to actions
+    fn typing(&self) -> bool {
+        false
+    }
+
     fn handle_events(&mut self, event: Option<Event>) -> Result<Option<Action>> {
         let r = match event {
             Some(Event::Key(key_event)) => self.handle_key_events(key_event)?,
@@ -65,6 +88,15 @@ pub trait Page {
     }
     fn draw(&mut self, f: &mut Frame<'_>, rect: Rect) -> Result<()>;
 
+    #[allow(unused_variables)]
+    fn update_background(&mut self, state: &mut BackgroundState) {
+    }
+
     fn pause(&mut self);
     fn resume(&mut self);
+
+    /// Whether the page keeps running while the help is shown, as it cannot be paused
+    fn runs_behind_help(&self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use ratatui::prelude::*;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Frame, Page, PageId};
+use crate::{
+    action::{act, Action, ActionState, Command, MutatorsAction},
+    components::menu::Menu,
+    config::{Config, PageKeyBindings, Settings},
+    pages::game::mutators::MUTATORS,
+};
+
+/// Picks the mutators of the next single player runs, before starting them
+pub struct MutatorsPage {
+    pub action_tx: Option<UnboundedSender<Action>>,
+    pub keymap: PageKeyBindings,
+    settings: Settings,
+    selected_option_index: usize, // Start, then the mutators
+}
+
+impl MutatorsPage {
+    pub fn new() -> Self {
+        MutatorsPage {
+            action_tx: None,
+            keymap: PageKeyBindings::default(),
+            settings: Settings::default(),
+            selected_option_index: 0,
+        }
+    }
+
+    fn send(&self, command: Command) -> Result<()> {
+        if let Some(action_tx) = &self.action_tx {
+            action_tx.send(act!(command))?;
+        }
+        Ok(())
+    }
+
+    fn select(&mut self) -> Result<()> {
+        match self.selected_option_index.checked_sub(1) {
+            None => self.send(Command::StartGame),
+            Some(index) => {
+                let mut settings = self.settings.clone();
+                settings.mutators.toggle(MUTATORS[index]);
+                self.send(Command::UpdateSettings(settings))
+            },
+        }
+    }
+}
+
+impl Page for MutatorsPage {
+    fn id(&self) -> PageId {
+        PageId::Mutators
+    }
+
+    fn register_keymap(&mut self, keymaps: &HashMap<PageId, PageKeyBindings>) -> Result<()> {
+        if let Some(keymap) = keymaps.get(&self.id()) {
+            self.keymap = keymap.clone();
+        }
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.settings = config.settings;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action.command {
+            Command::OpenMutators => self.selected_option_index = 0,
+            Command::Mutators(MutatorsAction::Up) => {
+                self.selected_option_index = self.selected_option_index.saturating_sub(1)
+            },
+            Command::Mutators(MutatorsAction::Down) => {
+                self.selected_option_index = (self.selected_option_index + 1).min(MUTATORS.len())
+            },
+            Command::Mutators(MutatorsAction::Select) => self.select()?,
+            Command::Mutators(MutatorsAction::Back) => self.send(Command::ShowHome)?,
+            _ => {},
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let mut options = vec!["Start".to_string()];
+        options.extend(MUTATORS.iter().map(|mutator| {
+            let mark = if self.settings.mutators.contains(*mutator) { "x" } else { " " };
+            format!("[{mark}] {}", mutator.title())
+        }));
+        let text = match self.selected_option_index.checked_sub(1) {
+            None => "Pick any twists for the run, they stack".to_string(),
+            Some(index) => MUTATORS[index].description().to_string(),
+        };
+        f.render_widget(Menu::new("Mutators", options, self.selected_option_index).text(vec![text]), area);
+        Ok(())
+    }
+
+    fn pause(&mut self) {
+    }
+
+    fn resume(&mut self) {
+    }
+}
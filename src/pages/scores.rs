@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Frame, Page, PageId};
+use crate::{
+    action::{act, Action, ActionState, Command, ScoresAction},
+    config::{Config, LeaderboardConfig, PageKeyBindings},
+    constants::leaderboard,
+    leaderboard::Entry,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    NoServer,
+    Loading,
+    Loaded(Vec<Entry>),
+    Failed(String),
+}
+
+/// The best runs on the leaderboard server
+pub struct ScoresPage {
+    pub action_tx: Option<UnboundedSender<Action>>,
+    pub keymap: PageKeyBindings,
+    config: LeaderboardConfig,
+    status: Status,
+}
+
+impl ScoresPage {
+    pub fn new() -> Self {
+        ScoresPage {
+            action_tx: None,
+            keymap: PageKeyBindings::default(),
+            config: LeaderboardConfig::default(),
+            status: Status::NoServer,
+        }
+    }
+
+    /// Ask the server for the top list, which arrives as `Command::ScoresLoaded`
+    fn fetch(&mut self) {
+        let (Some(server), Some(action_tx)) = (self.config.server.clone(), self.action_tx.clone()) else {
+            self.status = Status::NoServer;
+            return;
+        };
+        self.status = Status::Loading;
+        tokio::spawn(async move {
+            let result = crate::leaderboard::top(&server, leaderboard::TOP_ENTRIES).await.map_err(|e| e.to_string());
+            let _ = action_tx.send(act!(Command::ScoresLoaded(result)));
+        });
+    }
+
+    fn draw_message(&self, f: &mut Frame<'_>, area: Rect, lines: Vec<Line>) {
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 6;
+        let height = lines.len() as u16 + 4;
+        let [area] = Layout::horizontal([Constraint::Length(width)]).flex(layout::Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)]).flex(layout::Flex::Center).areas(area);
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).alignment(Alignment::Center).block(self.block()), area);
+    }
+
+    fn draw_entries(&self, f: &mut Frame<'_>, area: Rect, entries: &[Entry]) {
+        let header = ["#", "Player", "Pipes", "Distance", "Difficulty", "Mutators"].into_iter().collect::<Row>().bold();
+        let rows = entries.iter().enumerate().map(|(place, entry)| {
+            let row = [
+                (place + 1).to_string(),
+                entry.name.clone(),
+                entry.score.to_string(),
+                entry.distance.to_string(),
+                format!("{:?}", entry.difficulty),
+                entry.mutators.labels(),
+            ]
+            .into_iter()
+            .collect::<Row>();
+            match entry.name == self.config.name {
+                true => row.style(Style::default().fg(Color::Yellow)),
+                false => row,
+            }
+        });
+        let widths = [
+            Constraint::Length(2),
+            Constraint::Length(leaderboard::MAX_NAME_LENGTH as u16),
+            Constraint::Length(5),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(leaderboard::MUTATORS_WIDTH),
+        ];
+        let table = Table::new(rows, widths).header(header).column_spacing(2);
+
+        let width = 2 + leaderboard::MAX_NAME_LENGTH as u16 + 5 + 8 + 10 + leaderboard::MUTATORS_WIDTH + 2 * 5 + 6;
+        let height = entries.len() as u16 + 1 + 4;
+        let [area] = Layout::horizontal([Constraint::Length(width)]).flex(layout::Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)]).flex(layout::Flex::Center).areas(area);
+        f.render_widget(Clear, area);
+        f.render_widget(table.block(self.block()), area);
+    }
+
+    fn block(&self) -> Block<'static> {
+        Block::default()
+            .title("Global Scores")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Cyan))
+            .padding(Padding::symmetric(2, 1))
+            .style(Style::default().bg(Color::Black))
+    }
+}
+
+impl Page for ScoresPage {
+    fn id(&self) -> PageId {
+        PageId::Scores
+    }
+
+    fn register_keymap(&mut self, keymaps: &HashMap<PageId, PageKeyBindings>) -> Result<()> {
+        if let Some(keymap) = keymaps.get(&self.id()) {
+            self.keymap = keymap.clone();
+        }
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config.leaderboard;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action.command {
+            Command::OpenScores | Command::Scores(ScoresAction::Refresh) => self.fetch(),
+            Command::ScoresLoaded(result) => {
+                self.status = match result {
+                    Ok(entries) => Status::Loaded(entries),
+                    Err(e) => Status::Failed(e),
+                };
+            },
+            Command::Scores(ScoresAction::Back) => {
+                if let Some(action_tx) = &self.action_tx {
+                    action_tx.send(act!(Command::ShowHome))?;
+                }
+            },
+            _ => {},
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let hint = Line::from("r: refresh, Esc: back").dark_gray();
+        match &self.status {
+            Status::NoServer => {
+                let lines = vec![
+                    Line::from("No leaderboard server is configured."),
+                    Line::from("Set `leaderboard.server` in the config file."),
+                    Line::default(),
+                    hint,
+                ];
+                self.draw_message(f, area, lines);
+            },
+            Status::Loading => self.draw_message(f, area, vec![Line::from("Loading...")]),
+            Status::Failed(e) => {
+                let lines = vec![
+                    Line::from("Could not reach the leaderboard:"),
+                    Line::from(e.as_str()).red(),
+                    Line::default(),
+                    hint,
+                ];
+                self.draw_message(f, area, lines);
+            },
+            Status::Loaded(entries) if entries.is_empty() => {
+                self.draw_message(f, area, vec![Line::from("No runs yet."), Line::default(), hint]);
+            },
+            Status::Loaded(entries) => self.draw_entries(f, area, entries),
+        }
+        Ok(())
+    }
+
+    fn pause(&mut self) {
+    }
+
+    fn resume(&mut self) {
+    }
+}
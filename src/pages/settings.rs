@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use ratatui::prelude::*;
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Frame, Page, PageId};
+use crate::{
+    action::{act, Action, ActionState, Command, SettingsAction},
+    components::menu::Menu,
+    config::{Config, Difficulty, PageKeyBindings, RenderMode, Settings},
+    skin::Skin,
+};
+
+const RENDER_MODES: [RenderMode; 3] = [RenderMode::Text, RenderMode::HalfBlock, RenderMode::Braille];
+const DIFFICULTIES: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+const ASSIST_SPEEDS: [u8; 6] = [100, 90, 80, 70, 60, 50]; // Percent
+const SKIP_AFTER: [u32; 4] = [0, 3, 5, 10]; // Failed runs
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum OptionItem {
+    RenderMode,
+    Skin,
+    Difficulty,
+    PauseOnFocusLoss,
+    MinimalHud,
+    GameSpeed,
+    WiderGaps,
+    Invincible,
+    SkipAfter,
+    Back,
+}
+
+const OPTIONS: [OptionItem; 10] = [
+    OptionItem::RenderMode,
+    OptionItem::Skin,
+    OptionItem::Difficulty,
+    OptionItem::PauseOnFocusLoss,
+    OptionItem::MinimalHud,
+    OptionItem::GameSpeed,
+    OptionItem::WiderGaps,
+    OptionItem::Invincible,
+    OptionItem::SkipAfter,
+    OptionItem::Back,
+];
+
+fn cycle<T: PartialEq + Clone>(values: &[T], current: &T, step: isize) -> T {
+    let index = values.iter().position(|value| value == current).unwrap_or_default() as isize;
+    values[(index + step).rem_euclid(values.len() as isize) as usize].clone()
+}
+
+pub struct SettingsPage {
+    pub action_tx: Option<UnboundedSender<Action>>,
+    pub keymap: PageKeyBindings,
+    settings: Settings,
+    skins: Vec<Skin>,
+    selected_option_index: usize,
+}
+
+impl SettingsPage {
+    pub fn new() -> Self {
+        SettingsPage {
+            action_tx: None,
+            keymap: PageKeyBindings::default(),
+            settings: Settings::default(),
+            skins: Skin::builtin(),
+            selected_option_index: 0,
+        }
+    }
+
+    fn selected_option(&self) -> OptionItem {
+        OPTIONS[self.selected_option_index]
+    }
+
+    fn send(&self, command: Command) -> Result<()> {
+        if let Some(action_tx) = &self.action_tx {
+            action_tx.send(act!(command))?;
+        }
+        Ok(())
+    }
+
+    fn change(&mut self, step: isize) -> Result<()> {
+        let mut settings = self.settings.clone();
+        match self.selected_option() {
+            OptionItem::RenderMode => settings.render_mode = cycle(&RENDER_MODES, &settings.render_mode, step),
+            OptionItem::Skin => {
+                let names = self.skins.iter().map(|skin| skin.name.clone()).collect::<Vec<_>>();
+                settings.skin = cycle(&names, &Skin::find(&self.skins, &settings.skin).name, step);
+            },
+            OptionItem::Difficulty => settings.difficulty = cycle(&DIFFICULTIES, &settings.difficulty, step),
+            OptionItem::PauseOnFocusLoss => settings.pause_on_focus_loss = !settings.pause_on_focus_loss,
+            OptionItem::MinimalHud => settings.minimal_hud = !settings.minimal_hud,
+            OptionItem::GameSpeed => settings.assist.speed = cycle(&ASSIST_SPEEDS, &settings.assist.speed, step),
+            OptionItem::WiderGaps => settings.assist.wider_gaps = !settings.assist.wider_gaps,
+            OptionItem::Invincible => settings.assist.invincible = !settings.assist.invincible,
+            OptionItem::SkipAfter => settings.assist.skip_after = cycle(&SKIP_AFTER, &settings.assist.skip_after, step),
+            OptionItem::Back => return Ok(()),
+        }
+        self.send(Command::UpdateSettings(settings))
+    }
+
+    fn option_title(&self, item: OptionItem) -> String {
+        match item {
+            OptionItem::RenderMode => format!("Render mode: < {:?} >", self.settings.render_mode),
+            OptionItem::Skin => format!("Skin: < {} >", Skin::find(&self.skins, &self.settings.skin).name),
+            OptionItem::Difficulty => format!("Difficulty: < {:?} >", self.settings.difficulty),
+            OptionItem::PauseOnFocusLoss => {
+                format!("Pause on focus loss: < {} >", if self.settings.pause_on_focus_loss { "On" } else { "Off" })
+            },
+            OptionItem::MinimalHud => {
+                format!("Speedrun HUD: < {} >", if self.settings.minimal_hud { "Timer only" } else { "Full" })
+            },
+            OptionItem::GameSpeed => format!("Assist, game speed: < {}% >", self.settings.assist.speed),
+            OptionItem::WiderGaps => {
+                format!("Assist, wider gaps: < {} >", if self.settings.assist.wider_gaps { "On" } else { "Off" })
+            },
+            OptionItem::Invincible => {
+                format!("Assist, invincibility: < {} >", if self.settings.assist.invincible { "On" } else { "Off" })
+            },
+            OptionItem::SkipAfter => {
+                match self.settings.assist.skip_after {
+                    0 => "Assist, offer the card: < Never >".to_string(),
+                    runs => format!("Assist, offer the card: < After {runs} failed runs >"),
+                }
+            },
+            OptionItem::Back => "Back".to_string(),
+        }
+    }
+}
+
+impl Page for SettingsPage {
+    fn id(&self) -> PageId {
+        PageId::Settings
+    }
+
+    fn register_keymap(&mut self, keymaps: &HashMap<PageId, PageKeyBindings>) -> Result<()> {
+        if let Some(keymap) = keymaps.get(&self.id()) {
+            self.keymap = keymap.clone();
+        }
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.skins = Skin::load_all();
+        self.settings = config.settings;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Command::Settings(command) = action.command {
+            match command {
+                SettingsAction::Up => self.selected_option_index = self.selected_option_index.saturating_sub(1),
+                SettingsAction::Down => {
+                    self.selected_option_index = (self.selected_option_index + 1).min(OPTIONS.len() - 1)
+                },
+                SettingsAction::Left => self.change(-1)?,
+                SettingsAction::Right => self.change(1)?,
+                SettingsAction::Select if self.selected_option() == OptionItem::Back => {
+                    self.selected_option_index = 0;
+                    self.send(Command::CloseSettings)?;
+                },
+                SettingsAction::Select => self.change(1)?,
+                SettingsAction::Back => {
+                    self.selected_option_index = 0;
+                    self.send(Command::CloseSettings)?;
+                },
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let options = OPTIONS.iter().map(|item| self.option_title(*item)).collect();
+        f.render_widget(Menu::new("Settings", options, self.selected_option_index), area);
+        Ok(())
+    }
+
+    fn pause(&mut self) {
+    }
+
+    fn resume(&mut self) {
+    }
+}
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use ratatui::{prelude::*, symbols::Marker, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+
+use super::{Frame, Page, PageId};
+use crate::{
+    action::{act, Action, ActionState, Command, StatsAction},
+    config::PageKeyBindings,
+    constants::stats,
+    stats::History,
+};
+
+type Points = Vec<(f64, f64)>;
+
+pub struct StatsPage {
+    pub action_tx: Option<UnboundedSender<Action>>,
+    pub keymap: PageKeyBindings,
+    history: History,
+    status: Option<Line<'static>>, // Outcome of the last export
+}
+
+impl StatsPage {
+    pub fn new() -> Self {
+        StatsPage { action_tx: None, keymap: PageKeyBindings::default(), history: History::default(), status: None }
+    }
+
+    fn export(&mut self) {
+        self.status = Some(match self.history.export() {
+            Ok(paths) => {
+                let paths = paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>();
+                Line::from(format!("Exported to {}", paths.join(" and "))).green()
+            },
+            Err(e) => Line::from(format!("Failed to export: {e}")).red(),
+        });
+    }
+
+    fn totals(&self) -> Vec<Line<'static>> {
+        let totals = self.history.totals();
+        let [floor, ceiling, pipe] = totals.deaths;
+        let duration = totals.duration as u32;
+        vec![
+            Line::from(format!(
+                "Runs: {}   Pipes: {}   Best: {}   Average: {:.1}",
+                totals.runs,
+                totals.pipes,
+                totals.best,
+                totals.average()
+            )),
+            Line::from(format!("Flaps: {}   Time flown: {}:{:02}", totals.flaps, duration / 60, duration % 60)),
+            Line::from(format!(
+                "Crashed into the floor {floor}, the ceiling {ceiling} and pipes {pipe} times. Made it through {} times.",
+                totals.finished
+            )),
+            Line::from(format!("Assisted runs: {}   Best with assists: {}", totals.assisted, totals.assisted_best)),
+        ]
+    }
+
+    /// Score and best score so far of the most recent runs, by run number
+    fn chart_data(&self) -> (Points, Points) {
+        let skipped = self.history.0.len().saturating_sub(stats::CHART_RUNS);
+        let mut best = self.history.0[..skipped].iter().map(|run| run.score).max().unwrap_or(0);
+        let mut scores = Vec::new();
+        let mut bests = Vec::new();
+        for (index, run) in self.history.0.iter().enumerate().skip(skipped) {
+            best = best.max(run.score);
+            scores.push(((index + 1) as f64, run.score as f64));
+            bests.push(((index + 1) as f64, best as f64));
+        }
+        (scores, bests)
+    }
+
+    fn draw_chart(&self, f: &mut Frame<'_>, area: Rect) {
+        let (scores, bests) = self.chart_data();
+        let first = scores.first().map(|(run, _)| *run).unwrap_or(1.0);
+        let last = scores.last().map(|(run, _)| *run).unwrap_or(1.0).max(first + 1.0);
+        let top = bests.last().map(|(_, best)| *best).unwrap_or(0.0).max(1.0);
+
+        let datasets = vec![
+            Dataset::default()
+                .name("Score")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Cyan))
+                .data(&scores),
+            Dataset::default()
+                .name("Best")
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(Color::Yellow))
+                .data(&bests),
+        ];
+        let chart = Chart::new(datasets)
+            .block(Block::default().title("Score per run"))
+            .x_axis(
+                Axis::default()
+                    .title("Run")
+                    .style(Style::default().fg(Color::DarkGray))
+                    .bounds([first, last])
+                    .labels([format!("{first}"), format!("{last}")]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(Color::DarkGray))
+                    .bounds([0.0, top])
+                    .labels(["0".to_string(), format!("{top}")]),
+            )
+            .legend_position(Some(LegendPosition::TopLeft));
+        f.render_widget(chart, area);
+    }
+}
+
+impl Page for StatsPage {
+    fn id(&self) -> PageId {
+        PageId::Stats
+    }
+
+    fn register_keymap(&mut self, keymaps: &HashMap<PageId, PageKeyBindings>) -> Result<()> {
+        if let Some(keymap) = keymaps.get(&self.id()) {
+            self.keymap = keymap.clone();
+        }
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action.command {
+            // Runs are recorded in the game, and saved right away
+            Command::OpenStats => {
+                self.history = History::load();
+                self.status = None;
+            },
+            Command::Stats(StatsAction::Export) => self.export(),
+            Command::Stats(StatsAction::Back) => {
+                if let Some(action_tx) = &self.action_tx {
+                    action_tx.send(act!(Command::ShowHome))?;
+                }
+            },
+            _ => {},
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        let [area] = Layout::horizontal([Constraint::Percentage(80)]).flex(layout::Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Percentage(80)]).flex(layout::Flex::Center).areas(area);
+        let block = Block::default()
+            .title("Statistics")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Cyan))
+            .padding(Padding::symmetric(2, 1))
+            .style(Style::default().bg(Color::Black));
+        let inner = block.inner(area);
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+
+        let hint = self.status.clone().unwrap_or(Line::from("e: export to CSV and JSON, Esc: back").dark_gray());
+        if self.history.0.is_empty() {
+            let lines = vec![Line::from("No runs yet. Fly a few and come back!"), Line::default(), hint];
+            f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), inner);
+            return Ok(());
+        }
+
+        let [totals_area, sparkline_area, chart_area, hint_area] = Layout::vertical([
+            Constraint::Length(4),
+            Constraint::Length(5),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ])
+        .spacing(1)
+        .areas(inner);
+        f.render_widget(Paragraph::new(self.totals()), totals_area);
+
+        // One bar per run, as many recent runs as fit
+        let scores = self.history.0.iter().map(|run| run.score as u64).collect::<Vec<_>>();
+        let recent = &scores[scores.len().saturating_sub(sparkline_area.width as usize)..];
+        let sparkline = Sparkline::default()
+            .block(Block::default().title("Recent scores"))
+            .data(recent)
+            .style(Style::default().fg(Color::LightGreen));
+        f.render_widget(sparkline, sparkline_area);
+
+        self.draw_chart(f, chart_area);
+        f.render_widget(Paragraph::new(hint), hint_area);
+        Ok(())
+    }
+
+    fn pause(&mut self) {
+    }
+
+    fn resume(&mut self) {
+    }
+}
@@ -0,0 +1,342 @@
+use std::collections::HashMap;
+
+use color_eyre::eyre::Result;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::{prelude::*, widgets::*};
+use tokio::sync::mpsc::UnboundedSender;
+use tui_input::{backend::crossterm::EventHandler, Input};
+
+use super::{Frame, Page, PageId};
+use crate::{
+    action::{act, Action, ActionState, Command, TournamentAction},
+    components::menu::Menu,
+    config::PageKeyBindings,
+    constants::tournament,
+};
+
+/// Players and scores of a tournament. Everyone flies the same course in a round.
+#[derive(Debug)]
+struct Tournament {
+    names: Vec<String>,
+    seeds: Vec<u64>,       // Seed of the course of each round
+    scores: Vec<Vec<u16>>, // Pipes passed by each player, one entry per round played
+    round: usize,
+    turn: usize, // Index of the player to fly next
+}
+
+impl Tournament {
+    fn new(names: Vec<String>, rounds: usize) -> Self {
+        let scores = vec![Vec::new(); names.len()];
+        Tournament { names, seeds: (0..rounds).map(|_| rand::random()).collect(), scores, round: 0, turn: 0 }
+    }
+
+    fn finished(&self) -> bool {
+        self.round >= self.seeds.len()
+    }
+
+    fn record(&mut self, score: u16) {
+        self.scores[self.turn].push(score);
+        self.turn += 1;
+        if self.turn == self.names.len() {
+            self.turn = 0;
+            self.round += 1;
+        }
+    }
+
+    fn total(&self, player: usize) -> u32 {
+        self.scores[player].iter().map(|&score| score as u32).sum()
+    }
+
+    fn best(&self, player: usize) -> u16 {
+        self.scores[player].iter().copied().max().unwrap_or(0)
+    }
+
+    /// Players from first to last place, by total score and then by best round
+    fn ranking(&self) -> Vec<usize> {
+        let mut ranking = (0..self.names.len()).collect::<Vec<_>>();
+        ranking.sort_by_key(|&player| std::cmp::Reverse((self.total(player), self.best(player))));
+        ranking
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Entry,            // Typing the names of the players
+    Standings(usize), // Between turns, with the index of the selected option
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum OptionItem {
+    Play, // The next turn
+    Home, // Leave the tournament for later
+    End,
+    New,
+}
+
+pub struct TournamentPage {
+    pub action_tx: Option<UnboundedSender<Action>>,
+    pub keymap: PageKeyBindings,
+    phase: Phase,
+    input: Input,
+    names: Vec<String>,
+    tournament: Option<Tournament>,
+}
+
+impl TournamentPage {
+    pub fn new() -> Self {
+        TournamentPage {
+            action_tx: None,
+            keymap: PageKeyBindings::default(),
+            phase: Phase::Entry,
+            input: Input::default(),
+            names: Vec::new(),
+            tournament: None,
+        }
+    }
+
+    fn send(&self, command: Command) -> Result<()> {
+        if let Some(action_tx) = &self.action_tx {
+            action_tx.send(act!(command))?;
+        }
+        Ok(())
+    }
+
+    fn options(&self) -> Vec<(OptionItem, String)> {
+        match &self.tournament {
+            Some(tournament) if !tournament.finished() => {
+                vec![
+                    (OptionItem::Play, format!("Fly: {}", tournament.names[tournament.turn])),
+                    (OptionItem::Home, "Back to Home".to_string()),
+                    (OptionItem::End, "End tournament".to_string()),
+                ]
+            },
+            _ => vec![(OptionItem::New, "New tournament".to_string()), (OptionItem::End, "Back to Home".to_string())],
+        }
+    }
+
+    fn select(&mut self, item: OptionItem) -> Result<()> {
+        match item {
+            OptionItem::Play => {
+                if let Some(tournament) = &self.tournament {
+                    let name = tournament.names[tournament.turn].clone();
+                    self.send(Command::StartTurn(name, tournament.seeds[tournament.round]))?;
+                }
+            },
+            OptionItem::Home => self.send(Command::ShowHome)?,
+            OptionItem::End => {
+                self.tournament = None;
+                self.phase = Phase::Entry;
+                self.send(Command::ShowHome)?;
+            },
+            // The names are kept for a rematch
+            OptionItem::New => {
+                self.tournament = None;
+                self.phase = Phase::Entry;
+            },
+        }
+        Ok(())
+    }
+
+    fn handle_entry_key(&mut self, key: KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Enter if !self.input.value().trim().is_empty() => {
+                if self.names.len() < tournament::MAX_PLAYERS {
+                    self.names.push(self.input.value().trim().to_string());
+                }
+                self.input.reset();
+            },
+            KeyCode::Enter if self.names.len() >= tournament::MIN_PLAYERS => {
+                self.tournament = Some(Tournament::new(self.names.clone(), tournament::ROUNDS));
+                self.phase = Phase::Standings(0);
+            },
+            KeyCode::Backspace if self.input.value().is_empty() => {
+                self.names.pop();
+            },
+            KeyCode::Esc => self.send(Command::ShowHome)?,
+            KeyCode::Char(_) if self.input.value().chars().count() >= tournament::MAX_NAME_LENGTH => {},
+            _ => {
+                self.input.handle_event(&Event::Key(key));
+            },
+        }
+        Ok(())
+    }
+
+    fn draw_entry(&self, f: &mut Frame<'_>, area: Rect) {
+        let mut lines = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| Line::from(format!("{}. {name}", index + 1)))
+            .collect::<Vec<_>>();
+        if self.names.len() < tournament::MAX_PLAYERS {
+            lines.push(Line::from(vec![
+                Span::raw(format!("{}. ", self.names.len() + 1)),
+                Span::styled(format!("{}_", self.input.value()), Style::default().fg(Color::Yellow)),
+            ]));
+        }
+        lines.push(Line::default());
+        lines.push(Line::from("Enter: add a player").dark_gray());
+        if self.names.len() >= tournament::MIN_PLAYERS {
+            lines.push(Line::from("Enter on an empty name: start").dark_gray());
+        }
+        lines.push(Line::from("Backspace on an empty name: remove the last player").dark_gray());
+
+        let width = lines.iter().map(|line| line.width()).max().unwrap_or(0) as u16 + 6;
+        let height = lines.len() as u16 + 4;
+        let [area] = Layout::horizontal([Constraint::Length(width)]).flex(layout::Flex::Center).areas(area);
+        let [area] = Layout::vertical([Constraint::Length(height)]).flex(layout::Flex::Center).areas(area);
+        let block = Block::default()
+            .title("Tournament players")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Cyan))
+            .padding(Padding::symmetric(2, 1))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(Clear, area);
+        f.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_standings(&self, f: &mut Frame<'_>, area: Rect, tournament: &Tournament, selected: usize) {
+        let rounds = tournament.seeds.len();
+        let title = match tournament.finished() {
+            true => "Final standings".to_string(),
+            false => format!("Standings: round {} of {rounds}", tournament.round + 1),
+        };
+
+        let header = ["#", "Player"]
+            .into_iter()
+            .map(str::to_string)
+            .chain((1..=rounds).map(|round| format!("R{round}")))
+            .chain(["Total".to_string()])
+            .collect::<Row>()
+            .bold();
+        let rows = tournament.ranking().into_iter().enumerate().map(|(place, player)| {
+            let scores = (0..rounds).map(|round| {
+                tournament.scores[player].get(round).map(|score| score.to_string()).unwrap_or("-".to_string())
+            });
+            let row = [(place + 1).to_string(), tournament.names[player].clone()]
+                .into_iter()
+                .chain(scores)
+                .chain([tournament.total(player).to_string()])
+                .collect::<Row>();
+            match (tournament.finished(), place, player == tournament.turn) {
+                (true, 0, _) => row.style(Style::default().fg(Color::Yellow).bold()),
+                (false, _, true) => row.style(Style::default().fg(Color::Cyan)),
+                _ => row,
+            }
+        });
+        let widths = [Constraint::Length(2), Constraint::Length(tournament::MAX_NAME_LENGTH as u16)]
+            .into_iter()
+            .chain(std::iter::repeat_n(Constraint::Length(3), rounds))
+            .chain([Constraint::Length(5)]);
+        let table = Table::new(rows, widths).header(header).column_spacing(2);
+
+        let table_width = 2 + tournament::MAX_NAME_LENGTH as u16 + 3 * rounds as u16 + 5 + 2 * (rounds as u16 + 2) + 6;
+        let table_height = tournament.names.len() as u16 + 1 + 4;
+        let options = self.options().into_iter().map(|(_, title)| title).collect();
+        let menu = Menu::new("", options, selected);
+        let (_, menu_height) = menu.size();
+
+        let [area] = Layout::horizontal([Constraint::Length(table_width)]).flex(layout::Flex::Center).areas(area);
+        let [table_area, menu_area] =
+            Layout::vertical([Constraint::Length(table_height), Constraint::Length(menu_height)])
+                .flex(layout::Flex::Center)
+                .spacing(1)
+                .areas(area);
+        let block = Block::default()
+            .title(title)
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::new().bold().fg(Color::Cyan))
+            .padding(Padding::symmetric(2, 1))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(Clear, table_area);
+        f.render_widget(table.block(block), table_area);
+        f.render_widget(menu, menu_area);
+    }
+}
+
+impl Page for TournamentPage {
+    fn id(&self) -> PageId {
+        PageId::Tournament
+    }
+
+    fn register_keymap(&mut self, keymaps: &HashMap<PageId, PageKeyBindings>) -> Result<()> {
+        if let Some(keymap) = keymaps.get(&self.id()) {
+            self.keymap = keymap.clone();
+        }
+        Ok(())
+    }
+
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn typing(&self) -> bool {
+        self.phase == Phase::Entry
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.phase == Phase::Entry && key.kind == KeyEventKind::Press {
+            self.handle_entry_key(key)?;
+        }
+        Ok(None)
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if let Command::TurnOver(score) = action.command {
+            if let Some(tournament) = self.tournament.as_mut() {
+                tournament.record(score);
+                self.phase = Phase::Standings(0);
+            }
+        }
+
+        if let (Phase::Standings(selected), Command::Tournament(command)) = (self.phase, action.command) {
+            let num_options = self.options().len();
+            match command {
+                TournamentAction::Up => self.phase = Phase::Standings(selected.saturating_sub(1)),
+                TournamentAction::Down => self.phase = Phase::Standings((selected + 1).min(num_options - 1)),
+                TournamentAction::Select => {
+                    self.phase = Phase::Standings(0);
+                    self.select(self.options()[selected.min(num_options - 1)].0)?;
+                },
+                TournamentAction::Back => self.send(Command::ShowHome)?,
+            }
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        match (self.phase, &self.tournament) {
+            (Phase::Standings(selected), Some(tournament)) => self.draw_standings(f, area, tournament, selected),
+            _ => self.draw_entry(f, area),
+        }
+        Ok(())
+    }
+
+    fn pause(&mut self) {
+    }
+
+    fn resume(&mut self) {
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ranking() {
+        let names = vec!["Ann".to_string(), "Bob".to_string(), "Cat".to_string()];
+        let mut tournament = Tournament::new(names, 2);
+        for score in [3, 5, 1, 4, 2, 7] {
+            tournament.record(score);
+        }
+        assert!(tournament.finished());
+        // Ann and Bob both have 7, but Bob had the better round
+        assert_eq!(tournament.ranking(), vec![2, 1, 0]);
+    }
+}
@@ -0,0 +1,203 @@
+use std::path::Path;
+
+use color_eyre::eyre::{eyre, Result};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assets::{self, Asset},
+    constants::game,
+    utils::get_config_dir,
+};
+
+/// A frame of the flap animation. Colors are shared with the skin.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SkinFrame {
+    pub layers: Vec<String>,
+    pub duration: f32,
+}
+
+/// Appearance of the player, loaded from the built-in constants or from `<config dir>/skins/*.yaml`:
+///
+/// ```yaml
+/// name: Penguin
+/// colors: [White, Yellow]
+/// layers:
+///   - |2
+///      .-.
+///     (   )
+///   - |2
+///
+///      o,o
+/// ```
+///
+/// `flap`, `fall` and `dead` optionally override the frames of the corresponding animations.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct Skin {
+    pub name: String,
+    pub layers: Vec<String>,
+    pub colors: Vec<Option<Color>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub flap: Vec<SkinFrame>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fall: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead: Option<Vec<String>>,
+}
+
+impl Skin {
+    pub fn new(name: &str, layers: &[&str], colors: &[Option<Color>]) -> Self {
+        Skin {
+            name: name.to_string(),
+            layers: layers.iter().map(|layer| layer.to_string()).collect(),
+            colors: colors.to_vec(),
+            flap: Vec::new(),
+            fall: None,
+            dead: None,
+        }
+    }
+
+    pub fn builtin() -> Vec<Skin> {
+        let mut bird = Skin {
+            flap: game::BIRD_FLAP_FRAMES
+                .iter()
+                .map(|(layers, duration)| {
+                    SkinFrame { layers: layers.iter().map(|layer| layer.to_string()).collect(), duration: *duration }
+                })
+                .collect(),
+            fall: Some(game::BIRD_FALL_TEXTS.iter().map(|layer| layer.to_string()).collect()),
+            dead: Some(game::BIRD_DEAD_TEXTS.iter().map(|layer| layer.to_string()).collect()),
+            ..Skin::new("Bird", &game::BIRD_TEXTS, &game::BIRD_COLORS)
+        };
+
+        // The bird asset file only carries the still layers, so it replaces the still frame and the animations keep
+        // the built-in art. The hitbox follows the layers, so the anchor is not used.
+        let asset = assets::get(assets::BIRD);
+        if Some(&asset) != Asset::builtin(assets::BIRD).as_ref() {
+            match (Skin { layers: asset.layers, colors: asset.colors, ..bird.clone() }).padded() {
+                Ok(custom) => bird = custom,
+                Err(e) => log::warn!("Failed to use the bird asset, using the built-in one: {e}"),
+            }
+        }
+
+        vec![bird].into_iter().chain(Skin::others()).collect()
+    }
+
+    fn others() -> Vec<Skin> {
+        vec![
+            Skin::new("Reindeer", &game::REINDEER_TEXTS, &game::REINDEER_COLORS),
+            Skin::new("Santa", &game::SANTA_TEXTS, &game::SANTA_COLORS),
+            Skin::new("Snowflake", &game::SNOWFLAKE_TEXTS, &game::SNOWFLAKE_COLORS),
+        ]
+    }
+
+    /// Built-in skins followed by the skins in the config directory. A file replaces the built-in skin of the same
+    /// name.
+    pub fn load_all() -> Vec<Skin> {
+        let mut skins = Skin::builtin();
+
+        let directory = get_config_dir().join("skins");
+        let Ok(entries) = std::fs::read_dir(&directory) else {
+            return skins;
+        };
+        let mut paths = entries.filter_map(|entry| entry.ok().map(|entry| entry.path())).collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths {
+            if !matches!(path.extension().and_then(|ext| ext.to_str()), Some("yaml" | "yml")) {
+                continue;
+            }
+            match Skin::load(&path) {
+                Ok(skin) => {
+                    skins.retain(|s| s.name != skin.name);
+                    skins.push(skin);
+                },
+                Err(e) => log::error!("Failed to load skin {}: {e}", path.display()),
+            }
+        }
+        skins
+    }
+
+    pub fn load(path: &Path) -> Result<Skin> {
+        let content = std::fs::read_to_string(path)?;
+        let skin: Skin = serde_yaml::from_str(&content)?;
+        skin.padded()
+    }
+
+    /// The skin with every layer of every frame padded to the same size, so frames can be clipped alike at the edges
+    /// of the canvas, and a color for every layer. Fails if a layer is empty.
+    fn padded(mut self) -> Result<Skin> {
+        let mut frames = vec![&mut self.layers];
+        frames.extend(self.flap.iter_mut().map(|frame| &mut frame.layers));
+        frames.extend(self.fall.iter_mut());
+        frames.extend(self.dead.iter_mut());
+
+        if frames.iter().any(|layers| layers.is_empty()) {
+            return Err(eyre!("Skin {} has a frame without layers", self.name));
+        }
+        assets::pad_layers(frames.into_iter().flatten().collect())
+            .map_err(|_| eyre!("Skin {} has an empty layer", self.name))?;
+        self.colors.resize(self.layers.len(), None);
+        Ok(self)
+    }
+
+    /// The skin called `name`, or the first built-in skin if there is none
+    pub fn find(skins: &[Skin], name: &str) -> Skin {
+        skins.iter().find(|skin| skin.name == name).unwrap_or(&skins[0]).clone()
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        let lines = self.layers.iter().map(|layer| layer.lines().filter(|line| !line.is_empty()).collect::<Vec<_>>());
+        let height = lines.clone().map(|lines| lines.len()).max().unwrap_or(0) as u16;
+        let width = lines.flatten().map(|line| line.chars().count()).max().unwrap_or(0) as u16;
+        (width, height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skin() {
+        let skin: Skin = serde_yaml::from_str(
+            r#"
+name: Penguin
+colors: [White, null]
+layers:
+  - |2
+     .-.
+    (   )
+  - |2
+
+     o o
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(skin.name, "Penguin");
+        assert_eq!(skin.colors, vec![Some(Color::White), None]);
+        assert_eq!(skin.size(), (5, 2));
+        assert!(skin.flap.is_empty());
+    }
+
+    #[test]
+    fn test_pad_skin() {
+        let skin: Skin = serde_yaml::from_str(
+            r#"
+name: Penguin
+colors: [White, null]
+layers: [" .-.\n(   )", "o o"]
+fall: [" .-.\n(   )\n ^ ^", "o o"]
+"#,
+        )
+        .unwrap();
+
+        let skin = skin.padded().unwrap();
+        assert_eq!(skin.layers, vec![" .-. \n(   )\n     \n", "o o  \n     \n     \n"]);
+        assert_eq!(skin.fall.as_ref().unwrap()[0], " .-. \n(   )\n ^ ^ \n");
+
+        let empty = Skin { dead: Some(vec![" \n".to_string()]), ..skin };
+        assert!(empty.padded().is_err());
+    }
+}
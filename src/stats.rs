@@ -0,0 +1,241 @@
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    constants::stats,
+    pages::game::{
+        course::{DeathCause, DeathSpot},
+        mutators::{Mutator, Mutators},
+        observer::{GameEvent, Observer},
+    },
+    utils::{get_data_dir, load_data, save_data},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunStats {
+    pub timestamp: u64, // Seconds since the epoch, when the run started
+    pub seed: u64,
+    pub score: u16,
+    pub duration: f32, // Seconds
+    pub flaps: u32,
+    pub death: Option<DeathCause>, // None if the bird made it through the last pipe
+    #[serde(default)]
+    pub spot: Option<DeathSpot>,
+    #[serde(default)]
+    pub mutators: Mutators,
+    #[serde(default)]
+    pub assisted: bool, // Flown with any assist on
+}
+
+/// Sums over all runs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Totals {
+    pub runs: usize,
+    pub pipes: u32,
+    pub best: u16, // Without assists
+    pub flaps: u32,
+    pub duration: f32,
+    pub deaths: [usize; 3], // By floor, ceiling and pipe
+    pub finished: usize,
+    pub assisted: usize, // Runs flown with assists
+    pub assisted_best: u16,
+}
+
+impl Totals {
+    pub fn average(&self) -> f32 {
+        if self.runs == 0 {
+            0.0
+        } else {
+            self.pipes as f32 / self.runs as f32
+        }
+    }
+}
+
+/// Every run recorded, saved to `<data dir>/stats.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct History(pub Vec<RunStats>);
+
+impl History {
+    pub fn load() -> Self {
+        load_data(stats::FILE).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        save_data(stats::FILE, self)
+    }
+
+    pub fn totals(&self) -> Totals {
+        let mut totals = Totals { runs: self.0.len(), ..Totals::default() };
+        for run in &self.0 {
+            totals.pipes += run.score as u32;
+            if run.assisted {
+                totals.assisted += 1;
+                totals.assisted_best = totals.assisted_best.max(run.score);
+            } else {
+                totals.best = totals.best.max(run.score);
+            }
+            totals.flaps += run.flaps;
+            totals.duration += run.duration;
+            match run.death {
+                Some(DeathCause::Floor) => totals.deaths[0] += 1,
+                Some(DeathCause::Ceiling) => totals.deaths[1] += 1,
+                Some(DeathCause::Pipe) => totals.deaths[2] += 1,
+                None => totals.finished += 1,
+            }
+        }
+        totals
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = "timestamp,seed,score,duration,flaps,death,pipe,x,y,mutators,assisted\n".to_string();
+        for run in &self.0 {
+            let death = match run.death {
+                Some(cause) => serde_plain::to_string(&cause).unwrap_or_default(),
+                None => String::new(),
+            };
+            let spot = match run.spot {
+                Some(DeathSpot { pipe, x, y }) => format!("{pipe},{x},{y}"),
+                None => ",,".to_string(),
+            };
+            let mutators = run.mutators.labels();
+            let RunStats { timestamp, seed, score, duration, flaps, assisted, .. } = run;
+            csv += &format!("{timestamp},{seed},{score},{duration:.2},{flaps},{death},{spot},{mutators},{assisted}\n");
+        }
+        csv
+    }
+
+    /// Where the runs ended in a crash
+    pub fn spots(&self) -> impl Iterator<Item = DeathSpot> + '_ {
+        self.0.iter().filter_map(|run| run.spot)
+    }
+
+    /// Write the runs as CSV and JSON to the data dir, and return the paths written
+    pub fn export(&self) -> Result<Vec<PathBuf>> {
+        let directory = get_data_dir();
+        std::fs::create_dir_all(&directory)?;
+        let csv = directory.join(stats::EXPORT_CSV);
+        let json = directory.join(stats::EXPORT_JSON);
+        std::fs::write(&csv, self.to_csv())?;
+        std::fs::write(&json, serde_json::to_string_pretty(&self.0)?)?;
+        Ok(vec![csv, json])
+    }
+}
+
+/// Adds every run of the player to the history
+pub struct StatsRecorder {
+    history: History,
+    run: Option<RunStats>, // Underway
+    persist: bool,         // Whether runs are saved
+}
+
+impl StatsRecorder {
+    pub fn new(history: History) -> Self {
+        StatsRecorder { history, run: None, persist: true }
+    }
+}
+
+impl Observer for StatsRecorder {
+    fn notify(&mut self, event: &GameEvent) {
+        match *event {
+            GameEvent::RunStarted { seed, mutators, assisted, .. } => {
+                let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+                self.run = Some(RunStats {
+                    timestamp,
+                    seed,
+                    score: 0,
+                    duration: 0.0,
+                    flaps: 0,
+                    death: None,
+                    spot: None,
+                    mutators,
+                    assisted,
+                });
+            },
+            GameEvent::Flap { .. } => {
+                if let Some(run) = self.run.as_mut() {
+                    run.flaps += 1;
+                }
+            },
+            GameEvent::PipePassed { .. } => {},
+            GameEvent::Collision { cause, spot, .. } => {
+                if let Some(run) = self.run.as_mut() {
+                    run.death = Some(cause);
+                    run.spot = spot;
+                }
+            },
+            GameEvent::RunFinished { score, time, .. } => {
+                if let Some(run) = self.run.take() {
+                    self.history.0.push(RunStats { score, duration: time, ..run });
+                    if self.persist {
+                        if let Err(e) = self.history.save() {
+                            log::error!("Failed to save stats: {e}");
+                        }
+                    }
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history() {
+        let spot = DeathSpot { pipe: 3, x: -2, y: 9 };
+        let mut recorder = StatsRecorder::new(History::default());
+        recorder.persist = false;
+        let events = [
+            GameEvent::RunStarted {
+                seed: 7,
+                mutators: Mutators::default(),
+                assisted: false,
+                tutorial: false,
+                time: 0.0,
+            },
+            GameEvent::Flap { time: 0.5 },
+            GameEvent::Flap { time: 1.0 },
+            GameEvent::Collision { score: 3, time: 4.0, cause: DeathCause::Pipe, spot: Some(spot) },
+            GameEvent::RunFinished { score: 3, crashed: true, time: 4.0 },
+            GameEvent::RunStarted {
+                seed: 8,
+                mutators: vec![Mutator::Mirror, Mutator::TinyBird].into(),
+                assisted: false,
+                tutorial: false,
+                time: 0.0,
+            },
+            GameEvent::RunFinished { score: 20, crashed: false, time: 30.0 },
+            GameEvent::RunStarted {
+                seed: 9,
+                mutators: Mutators::default(),
+                assisted: true,
+                tutorial: false,
+                time: 0.0,
+            },
+            GameEvent::RunFinished { score: 20, crashed: false, time: 60.0 },
+        ];
+        for event in events {
+            recorder.notify(&event);
+        }
+
+        let history = recorder.history;
+        let totals = history.totals();
+        assert_eq!((totals.runs, totals.pipes, totals.best, totals.flaps), (3, 43, 20, 2));
+        assert_eq!((totals.deaths, totals.finished), ([0, 0, 1], 2));
+        assert_eq!((totals.assisted, totals.assisted_best), (1, 20));
+
+        let timestamp = history.0[0].timestamp;
+        let csv = history.to_csv();
+        let header = "timestamp,seed,score,duration,flaps,death,pipe,x,y,mutators,assisted";
+        assert!(csv.starts_with(&format!("{header}\n{timestamp},7,3,4.00,2,pipe,3,-2,9,,false\n")));
+        assert!(csv.contains(",8,20,30.00,0,,,,,mir tiny,false\n"));
+        assert!(csv.ends_with(",9,20,60.00,0,,,,,,true\n"));
+        assert_eq!(history.spots().collect::<Vec<_>>(), vec![spot]);
+    }
+}
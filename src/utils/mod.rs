@@ -5,6 +5,7 @@ use std::path::PathBuf;
 use color_eyre::eyre::Result;
 use directories::ProjectDirs;
 use lazy_static::lazy_static;
+use serde::{de::DeserializeOwned, Serialize};
 use tracing::error;
 use tracing_error::ErrorLayer;
 use tracing_subscriber::{self, prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, Layer};
@@ -88,6 +89,65 @@ pub fn get_config_dir() -> PathBuf {
     directory
 }
 
+pub fn load_data<T: DeserializeOwned>(file: &str) -> Option<T> {
+    let path = get_data_dir().join(file);
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            log::error!("Failed to parse {}: {e}", path.display());
+            None
+        },
+    }
+}
+
+pub fn save_data<T: Serialize>(file: &str, value: &T) -> Result<()> {
+    let directory = get_data_dir();
+    std::fs::create_dir_all(&directory)?;
+    std::fs::write(directory.join(file), serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+pub fn remove_data(file: &str) -> Result<()> {
+    let path = get_data_dir().join(file);
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Year, month and day of a date given in days since 1970-01-01
+pub fn civil_date(days: i64) -> (i64, u32, u32) {
+    // Howard Hinnant's `civil_from_days`, with eras of 400 years starting on March 1st
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 in the local time zone
+pub fn local_day() -> i64 {
+    let seconds =
+        std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+    #[allow(unused_mut)]
+    let mut offset = 0;
+    #[cfg(unix)]
+    {
+        // SAFETY: `localtime_r` only writes to the `tm` passed in
+        let mut tm = unsafe { std::mem::zeroed::<libc::tm>() };
+        if !unsafe { libc::localtime_r(&(seconds as libc::time_t), &mut tm) }.is_null() {
+            offset = tm.tm_gmtoff as i64;
+        }
+    }
+    (seconds + offset).div_euclid(86400)
+}
+
 pub fn initialize_logging() -> Result<()> {
     let directory = get_data_dir();
     std::fs::create_dir_all(directory.clone())?;
@@ -135,3 +195,16 @@ macro_rules! trace_dbg {
         trace_dbg!(level: tracing::Level::DEBUG, $ex)
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(11016), (2000, 2, 29));
+        assert_eq!(civil_date(20812), (2026, 12, 25));
+        assert_eq!(civil_date(-1), (1969, 12, 31));
+    }
+}